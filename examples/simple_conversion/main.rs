@@ -13,6 +13,7 @@ fn main() -> Result<()> {
         only_annotated: true,     // Only process types with BorshSerialize
         ignored_patterns: vec![], // Don't ignore any files
         output_structure: OutputStructure::Nested, // Maintain directory structure
+        ..Config::default()
     };
 
     // Initialize the generator
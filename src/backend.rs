@@ -0,0 +1,296 @@
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+use crate::borsh_js_generator::BorshJsGenerator;
+use crate::borsh_schema_generator::BorshSchemaGenerator;
+use crate::code_generator::ZorshGenerator;
+use crate::dependency_resolver::TypeDependencies;
+use crate::json_schema_generator::JsonSchemaGenerator;
+use crate::python_generator::PythonGenerator;
+use crate::type_parser::{EnumInfo, StructInfo};
+use crate::{Config, ImportExtension, ModuleFormat, OutputStructure};
+
+/// A code generation target working on the parsed and resolved types
+pub trait Backend {
+    /// Generates the contents of a single module's file
+    fn generate_module(&self, module: &str, dependencies: &TypeDependencies) -> Result<String>;
+
+    /// Path of a module's generated file, relative to the output directory
+    fn module_file(&self, module: &str) -> PathBuf;
+
+    /// Renders the statement importing `type_names` from `from_module` into `module`
+    fn render_import(&self, module: &str, from_module: &str, type_names: &[String]) -> String;
+
+    /// Generates every output file, keyed by path relative to the output directory.
+    ///
+    /// Defaults to one file per module; backends that bundle modules or need
+    /// extra files (package markers, indexes) override this.
+    fn generate_files(
+        &self,
+        modules: &BTreeSet<String>,
+        dependencies: &TypeDependencies,
+    ) -> Result<BTreeMap<PathBuf, String>> {
+        let mut files = BTreeMap::new();
+        for module in modules {
+            files.insert(
+                self.module_file(module),
+                self.generate_module(module, dependencies)?,
+            );
+        }
+        Ok(files)
+    }
+}
+
+/// Built-in code generation backends
+#[derive(Debug, Clone, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackendKind {
+    /// Zorsh TypeScript schemas
+    #[default]
+    Zorsh,
+    /// Schema objects for the `borsh` npm package
+    BorshJs,
+    /// Python modules using `borsh-construct`
+    Python,
+    /// JSON Schema documents describing the decoded shape of each type
+    JsonSchema,
+    /// `BorshSchemaContainer` JSON, as produced by the `BorshSchema` derive
+    BorshSchema,
+}
+
+impl BackendKind {
+    /// Creates the backend for the given types
+    pub fn create(
+        &self,
+        structs: HashMap<String, StructInfo>,
+        enums: HashMap<String, EnumInfo>,
+        config: &Config,
+    ) -> Box<dyn Backend> {
+        match self {
+            BackendKind::Zorsh => {
+                Box::new(ZorshGenerator::new(structs, enums).with_config(config.clone()))
+            }
+            BackendKind::BorshJs => {
+                Box::new(BorshJsGenerator::new(structs, enums).with_config(config.clone()))
+            }
+            BackendKind::Python => {
+                Box::new(PythonGenerator::new(structs, enums).with_config(config.clone()))
+            }
+            BackendKind::JsonSchema => {
+                Box::new(JsonSchemaGenerator::new(structs, enums).with_config(config.clone()))
+            }
+            BackendKind::BorshSchema => {
+                Box::new(BorshSchemaGenerator::new(structs, enums).with_config(config.clone()))
+            }
+        }
+    }
+}
+
+/// Extension of the files the TypeScript backends write
+pub(crate) fn typescript_extension(config: &Config) -> &'static str {
+    match config.module_format {
+        ModuleFormat::Esm => "ts",
+        ModuleFormat::CommonJs => "cts",
+    }
+}
+
+/// Path of a module's TypeScript file, shared by the TypeScript backends
+pub(crate) fn typescript_module_file(module: &str, config: &Config) -> PathBuf {
+    let separator = match config.output_structure {
+        OutputStructure::Nested => "/",
+        OutputStructure::Flat => "_",
+    };
+    PathBuf::from(format!(
+        "{}.{}",
+        module.replace("::", separator).to_lowercase(),
+        typescript_extension(config)
+    ))
+}
+
+/// Specifier the generated TypeScript file `from` uses to import the file `to`
+pub(crate) fn typescript_import_specifier(from: &Path, to: &Path, config: &Config) -> String {
+    let extension = match (&config.import_extension, &config.module_format) {
+        (ImportExtension::None, _) => "",
+        (ImportExtension::Js, ModuleFormat::Esm) => ".js",
+        (ImportExtension::Js, ModuleFormat::CommonJs) => ".cjs",
+        (ImportExtension::Ts, ModuleFormat::Esm) => ".ts",
+        (ImportExtension::Ts, ModuleFormat::CommonJs) => ".cts",
+    };
+    format!(
+        "{}{}",
+        relative_path(from, &to.with_extension("")),
+        extension
+    )
+}
+
+/// Path of `to` relative to the directory of `from`, with forward slashes
+pub(crate) fn relative_path(from: &Path, to: &Path) -> String {
+    let from_dir: Vec<Component> = from
+        .parent()
+        .map(|dir| dir.components().collect())
+        .unwrap_or_default();
+    let to: Vec<Component> = to.components().collect();
+
+    let common = from_dir.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from_dir.len() - common];
+    parts.extend(
+        to[common..]
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned()),
+    );
+
+    let path = parts.join("/");
+    if path.starts_with("..") {
+        path
+    } else {
+        format!("./{}", path)
+    }
+}
+
+/// Names of the types defined in a module, in dependency order
+pub(crate) fn module_type_names(
+    module: &str,
+    structs: &HashMap<String, StructInfo>,
+    enums: &HashMap<String, EnumInfo>,
+    dependencies: &TypeDependencies,
+) -> Vec<String> {
+    dependencies
+        .ordered_types
+        .iter()
+        .filter_map(|type_path| {
+            if let Some(struct_info) = structs.get(type_path) {
+                (struct_info.module_path == module).then(|| struct_info.name.clone())
+            } else if let Some(enum_info) = enums.get(type_path) {
+                (enum_info.module_path == module).then(|| enum_info.name.clone())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// `index.ts` barrels re-exporting every module file and subdirectory barrel of each
+/// output directory, up to a root `index.ts`.
+///
+/// `exports` maps each module file to the names it exports. Entries whose names
+/// collide with another entry of the same barrel are re-exported as a namespace.
+/// Fails if a module file is itself named like a barrel, as it would be overwritten.
+pub(crate) fn typescript_barrels(
+    exports: &BTreeMap<PathBuf, Vec<String>>,
+    config: &Config,
+) -> Result<BTreeMap<PathBuf, String>> {
+    let index_file = PathBuf::from(format!("index.{}", typescript_extension(config)));
+    if let Some(file) = exports
+        .keys()
+        .find(|file| file.file_name() == Some(index_file.as_os_str()))
+    {
+        bail!(
+            "The module file {} would be overwritten by its directory's barrel, rename the module or disable barrels",
+            file.display()
+        );
+    }
+
+    let mut dirs = BTreeSet::new();
+    for file in exports.keys() {
+        let mut dir = file.parent();
+        while let Some(current) = dir {
+            dirs.insert(current.to_path_buf());
+            dir = current.parent();
+        }
+    }
+
+    // Children first, so each barrel knows what its subdirectories export
+    let mut dirs: Vec<_> = dirs.into_iter().collect();
+    dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+
+    let mut barrel_exports: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    let mut barrels = BTreeMap::new();
+
+    for dir in &dirs {
+        // (specifier, namespace, exported names)
+        let mut entries = Vec::new();
+        for (file, names) in exports {
+            if file.parent() == Some(dir.as_path()) {
+                let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+                let specifier = typescript_import_specifier(&dir.join(&index_file), file, config);
+                entries.push((specifier, stem.to_string(), names));
+            }
+        }
+        for (subdir, names) in &barrel_exports {
+            if subdir.parent() == Some(dir.as_path()) {
+                let name = subdir.file_name().unwrap_or_default().to_string_lossy();
+                let specifier = typescript_import_specifier(
+                    &dir.join(&index_file),
+                    &subdir.join(&index_file),
+                    config,
+                );
+                entries.push((specifier, name.to_string(), names));
+            }
+        }
+        entries.sort();
+
+        let mut name_counts: HashMap<&String, usize> = HashMap::new();
+        for (_, _, names) in &entries {
+            for name in names.iter() {
+                *name_counts.entry(name).or_default() += 1;
+            }
+        }
+
+        // Namespaces must not clash with each other or with the names exported as is
+        let mut taken: HashSet<String> = name_counts
+            .iter()
+            .filter(|(_, count)| **count == 1)
+            .map(|(name, _)| name.to_string())
+            .collect();
+
+        let mut content = String::new();
+        let mut names = Vec::new();
+        for (specifier, namespace, entry_names) in &entries {
+            if entry_names.iter().any(|name| name_counts[name] > 1) {
+                let base = typescript_identifier(namespace);
+                let mut namespace = base.clone();
+                let mut suffix = 2;
+                while !taken.insert(namespace.clone()) {
+                    namespace = format!("{}_{}", base, suffix);
+                    suffix += 1;
+                }
+                content.push_str(&format!(
+                    "export * as {} from '{}';\n",
+                    namespace, specifier
+                ));
+                names.push(namespace);
+            } else {
+                content.push_str(&format!("export * from '{}';\n", specifier));
+                names.extend(entry_names.iter().cloned());
+            }
+        }
+
+        barrels.insert(dir.join(&index_file), content);
+        barrel_exports.insert(dir.clone(), names);
+    }
+
+    Ok(barrels)
+}
+
+/// `name` with anything that can't appear in a TypeScript identifier replaced by `_`
+fn typescript_identifier(name: &str) -> String {
+    let identifier: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", identifier)
+    } else {
+        identifier
+    }
+}
@@ -0,0 +1,658 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{Map, Number, Value};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use crate::code_generator::is_tuple;
+use crate::ir::TypeIr;
+use crate::size::{Size, SizeAnalyzer};
+use crate::type_parser::{EnumInfo, EnumVariant, FieldInfo, StructInfo, TypeKind};
+
+/// Encodes and decodes Borsh by interpreting the parsed types, without compiling them.
+///
+/// Values are JSON shaped like Zorsh decodes them: structs are objects, enums are
+/// single-key objects (`{ "Variant": payload }`, with `{}` for unit variants), tuple
+/// variants with several fields are arrays, and maps with `String` keys are objects
+/// while other maps are arrays of `[key, value]` pairs. Integers wider than 32 bits
+/// are decimal strings, as JSON numbers can't hold them exactly, and infinite floats
+/// are `"Infinity"` or `"-Infinity"`.
+pub struct BorshCodec {
+    structs: HashMap<String, StructInfo>,
+    enums: HashMap<String, EnumInfo>,
+}
+
+impl BorshCodec {
+    pub fn new(structs: HashMap<String, StructInfo>, enums: HashMap<String, EnumInfo>) -> Self {
+        Self { structs, enums }
+    }
+
+    pub fn from_ir(ir: &TypeIr) -> Self {
+        Self::new(ir.struct_map(), ir.enum_map())
+    }
+
+    /// Full path of a type given by full path or, if unambiguous, by name
+    pub fn resolve_type(&self, type_path: &str) -> Result<String> {
+        if self.structs.contains_key(type_path) || self.enums.contains_key(type_path) {
+            return Ok(type_path.to_string());
+        }
+
+        let mut matches: Vec<_> = self
+            .structs
+            .iter()
+            .filter(|(_, s)| s.name == type_path)
+            .map(|(path, _)| path)
+            .chain(
+                self.enums
+                    .iter()
+                    .filter(|(_, e)| e.name == type_path)
+                    .map(|(path, _)| path),
+            )
+            .collect();
+        matches.sort();
+
+        match matches.as_slice() {
+            [path] => Ok(path.to_string()),
+            [] => bail!("Unknown type `{}`", type_path),
+            paths => bail!(
+                "`{}` is ambiguous, use one of: {}",
+                type_path,
+                paths
+                    .iter()
+                    .map(|path| path.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// Decodes a value of the type at `type_path`, which must use up all of `bytes`
+    pub fn decode(&self, type_path: &str, bytes: &[u8]) -> Result<Value> {
+        let type_kind = self.type_kind(type_path)?;
+        let mut reader = Reader { bytes, offset: 0 };
+        let value = self.decode_value(&type_kind, &mut reader)?;
+
+        if reader.offset != bytes.len() {
+            bail!(
+                "{} trailing bytes after the value, at offset {}",
+                bytes.len() - reader.offset,
+                reader.offset
+            );
+        }
+        Ok(value)
+    }
+
+    /// Encodes a value of the type at `type_path`
+    pub fn encode(&self, type_path: &str, value: &Value) -> Result<Vec<u8>> {
+        let type_kind = self.type_kind(type_path)?;
+        let mut output = Vec::new();
+        self.encode_value(&type_kind, value, &mut output)?;
+        Ok(output)
+    }
+
+    fn type_kind(&self, type_path: &str) -> Result<TypeKind> {
+        let type_path = self.resolve_type(type_path)?;
+        Ok(TypeKind::named(&type_path, &self.structs))
+    }
+
+    fn decode_value(&self, type_kind: &TypeKind, reader: &mut Reader) -> Result<Value> {
+        match type_kind {
+            TypeKind::Primitive(name) => decode_primitive(name, reader),
+            TypeKind::String => {
+                let len = reader.read_u32()? as usize;
+                let bytes = reader.read(len)?;
+                let string = String::from_utf8(bytes.to_vec()).context("Invalid UTF-8 string")?;
+                Ok(Value::String(string))
+            }
+            TypeKind::Struct(_, path) | TypeKind::Enum(_, path) => {
+                if let Some(struct_info) = self.structs.get(path) {
+                    self.decode_fields(&struct_info.fields, reader)
+                        .with_context(|| format!("in `{}`", path))
+                } else if let Some(enum_info) = self.enums.get(path) {
+                    let index = reader.read(1)?[0];
+                    let variant = enum_info
+                        .variants
+                        .get(index as usize)
+                        .ok_or_else(|| anyhow!("Invalid discriminant {} for `{}`", index, path))?;
+                    let payload = self
+                        .decode_variant(variant, reader)
+                        .with_context(|| format!("in `{}::{}`", path, variant.name))?;
+
+                    let mut object = Map::new();
+                    object.insert(variant.name.clone(), payload);
+                    Ok(Value::Object(object))
+                } else {
+                    bail!("No definition of `{}`", path)
+                }
+            }
+            TypeKind::Vec(inner) => {
+                let len = self.read_len(&[inner], reader)?;
+                let items = (0..len)
+                    .map(|index| {
+                        self.decode_value(inner, reader)
+                            .with_context(|| format!("at index {}", index))
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(Value::Array(items))
+            }
+            TypeKind::Array(inner, size) => {
+                let items = (0..*size)
+                    .map(|index| {
+                        self.decode_value(inner, reader)
+                            .with_context(|| format!("at index {}", index))
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(Value::Array(items))
+            }
+            TypeKind::Option(inner) => match reader.read(1)?[0] {
+                0 => Ok(Value::Null),
+                1 => self.decode_value(inner, reader),
+                tag => bail!("Invalid option tag {}", tag),
+            },
+            TypeKind::HashMap(key_kind, value_kind) => {
+                let len = self.read_len(&[key_kind, value_kind], reader)?;
+                let mut entries = Vec::new();
+                for index in 0..len {
+                    let key = self
+                        .decode_value(key_kind, reader)
+                        .with_context(|| format!("in key {}", index))?;
+                    let value = self
+                        .decode_value(value_kind, reader)
+                        .with_context(|| format!("in value {}", index))?;
+                    entries.push((key, value));
+                }
+
+                if matches!(**key_kind, TypeKind::String) {
+                    let object = entries
+                        .into_iter()
+                        .map(|(key, value)| (key.as_str().unwrap_or_default().to_string(), value))
+                        .collect();
+                    Ok(Value::Object(object))
+                } else {
+                    Ok(Value::Array(
+                        entries
+                            .into_iter()
+                            .map(|(key, value)| Value::Array(vec![key, value]))
+                            .collect(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Reads the length of a collection whose elements are made of `element`. Like
+    /// borsh-rs, zero-sized elements are rejected, as the length would be unbounded
+    /// by the input, and so are lengths the remaining bytes can't hold.
+    fn read_len(&self, element: &[&TypeKind], reader: &mut Reader) -> Result<usize> {
+        let len = reader.read_u32()? as usize;
+        let analyzer = SizeAnalyzer::new(&self.structs, &self.enums);
+        let sizes = element
+            .iter()
+            .map(|type_kind| analyzer.type_size(type_kind))
+            .collect::<Result<Vec<_>>>();
+        // Undefined types fail once decoded, with a better error
+        let Ok(sizes) = sizes else {
+            return Ok(len);
+        };
+
+        if len > 0 && sizes.iter().all(|size| *size == Size::Fixed(0)) {
+            bail!("Collections of zero-sized types are not supported");
+        }
+        let min: usize = sizes.iter().map(Size::min).sum();
+        if len.saturating_mul(min) > reader.remaining() {
+            bail!(
+                "Length {} needs at least {} bytes, found {} at offset {}",
+                len,
+                len.saturating_mul(min),
+                reader.remaining(),
+                reader.offset
+            );
+        }
+        Ok(len)
+    }
+
+    fn decode_fields(&self, fields: &[FieldInfo], reader: &mut Reader) -> Result<Value> {
+        let mut object = Map::new();
+        for field in fields {
+            let value = self
+                .decode_value(&field.type_kind, reader)
+                .with_context(|| format!("in field `{}`", field.name))?;
+            object.insert(field.name.clone(), value);
+        }
+        Ok(Value::Object(object))
+    }
+
+    fn decode_variant(&self, variant: &EnumVariant, reader: &mut Reader) -> Result<Value> {
+        match &variant.fields {
+            None => Ok(Value::Object(Map::new())),
+            Some(fields) if is_tuple(fields) => {
+                let mut values = fields
+                    .iter()
+                    .map(|field| self.decode_value(&field.type_kind, reader))
+                    .collect::<Result<Vec<_>>>()?;
+                if values.len() == 1 {
+                    Ok(values.remove(0))
+                } else {
+                    Ok(Value::Array(values))
+                }
+            }
+            Some(fields) => self.decode_fields(fields, reader),
+        }
+    }
+
+    fn encode_value(
+        &self,
+        type_kind: &TypeKind,
+        value: &Value,
+        output: &mut Vec<u8>,
+    ) -> Result<()> {
+        match type_kind {
+            TypeKind::Primitive(name) => encode_primitive(name, value, output),
+            TypeKind::String => {
+                let string = value
+                    .as_str()
+                    .ok_or_else(|| anyhow!("Expected a string, found {}", value))?;
+                write_len(string.len(), output)?;
+                output.extend_from_slice(string.as_bytes());
+                Ok(())
+            }
+            TypeKind::Struct(_, path) | TypeKind::Enum(_, path) => {
+                if let Some(struct_info) = self.structs.get(path) {
+                    self.encode_fields(&struct_info.fields, value, output)
+                        .with_context(|| format!("in `{}`", path))
+                } else if let Some(enum_info) = self.enums.get(path) {
+                    self.encode_enum(enum_info, value, output)
+                        .with_context(|| format!("in `{}`", path))
+                } else {
+                    bail!("No definition of `{}`", path)
+                }
+            }
+            TypeKind::Vec(inner) => {
+                let items = value
+                    .as_array()
+                    .ok_or_else(|| anyhow!("Expected an array, found {}", value))?;
+                write_len(items.len(), output)?;
+                for (index, item) in items.iter().enumerate() {
+                    self.encode_value(inner, item, output)
+                        .with_context(|| format!("at index {}", index))?;
+                }
+                Ok(())
+            }
+            TypeKind::Array(inner, size) => {
+                let items = value
+                    .as_array()
+                    .ok_or_else(|| anyhow!("Expected an array, found {}", value))?;
+                if items.len() != *size {
+                    bail!("Expected {} elements, found {}", size, items.len());
+                }
+                for (index, item) in items.iter().enumerate() {
+                    self.encode_value(inner, item, output)
+                        .with_context(|| format!("at index {}", index))?;
+                }
+                Ok(())
+            }
+            TypeKind::Option(inner) => {
+                if value.is_null() {
+                    output.push(0);
+                    Ok(())
+                } else {
+                    output.push(1);
+                    self.encode_value(inner, value, output)
+                }
+            }
+            TypeKind::HashMap(key_kind, value_kind) => {
+                let mut entries: Vec<(Value, &Value)> = match value {
+                    Value::Object(object) => object
+                        .iter()
+                        .map(|(key, value)| (Value::String(key.clone()), value))
+                        .collect(),
+                    Value::Array(pairs) => pairs
+                        .iter()
+                        .map(|pair| match pair.as_array().map(Vec::as_slice) {
+                            Some([key, value]) => Ok((key.clone(), value)),
+                            _ => Err(anyhow!("Expected a [key, value] pair, found {}", pair)),
+                        })
+                        .collect::<Result<_>>()?,
+                    _ => bail!("Expected an object or an array of pairs, found {}", value),
+                };
+                // Borsh writes map entries in key order
+                entries.sort_by(|(a, _), (b, _)| self.compare_keys(key_kind, a, b));
+
+                write_len(entries.len(), output)?;
+                let mut keys = HashSet::new();
+                for (key, value) in entries {
+                    let mut encoded = Vec::new();
+                    self.encode_value(key_kind, &key, &mut encoded)
+                        .with_context(|| format!("in key {}", key))?;
+                    if !keys.insert(encoded.clone()) {
+                        bail!("Duplicate key {} in map", key);
+                    }
+                    output.extend(encoded);
+                    self.encode_value(value_kind, value, output)
+                        .with_context(|| format!("in the value of {}", key))?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn encode_fields(
+        &self,
+        fields: &[FieldInfo],
+        value: &Value,
+        output: &mut Vec<u8>,
+    ) -> Result<()> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| anyhow!("Expected an object, found {}", value))?;
+        for field in fields {
+            let field_value = object
+                .get(&field.name)
+                .ok_or_else(|| anyhow!("Missing field `{}`", field.name))?;
+            self.encode_value(&field.type_kind, field_value, output)
+                .with_context(|| format!("in field `{}`", field.name))?;
+        }
+        Ok(())
+    }
+
+    fn encode_enum(&self, enum_info: &EnumInfo, value: &Value, output: &mut Vec<u8>) -> Result<()> {
+        let (index, variant, payload) = variant_of(enum_info, value)?;
+        let name = &variant.name;
+        output.push(
+            u8::try_from(index)
+                .with_context(|| format!("Too many variants in `{}`", enum_info.name))?,
+        );
+
+        match &variant.fields {
+            None => Ok(()),
+            Some(fields) if is_tuple(fields) && fields.len() == 1 => self
+                .encode_value(&fields[0].type_kind, payload, output)
+                .with_context(|| format!("in `{}`", name)),
+            Some(fields) if is_tuple(fields) => {
+                let items = payload
+                    .as_array()
+                    .filter(|items| items.len() == fields.len())
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Expected an array of {} values, found {}",
+                            fields.len(),
+                            payload
+                        )
+                    })?;
+                for (field, item) in fields.iter().zip(items) {
+                    self.encode_value(&field.type_kind, item, output)
+                        .with_context(|| format!("in `{}`", name))?;
+                }
+                Ok(())
+            }
+            Some(fields) => self
+                .encode_fields(fields, payload, output)
+                .with_context(|| format!("in `{}`", name)),
+        }
+    }
+
+    /// Orders map keys like their Rust types do, which is the order Borsh writes map
+    /// entries in: strings bytewise, integers numerically, structs field by field and
+    /// enums by variant, then payload. Values that don't match the type compare equal,
+    /// as encoding them fails anyway.
+    fn compare_keys(&self, type_kind: &TypeKind, a: &Value, b: &Value) -> Ordering {
+        match type_kind {
+            TypeKind::Primitive(name) => compare_primitives(name, a, b),
+            TypeKind::String => a.as_str().cmp(&b.as_str()),
+            TypeKind::Struct(_, path) | TypeKind::Enum(_, path) => {
+                if let Some(struct_info) = self.structs.get(path) {
+                    self.compare_fields(&struct_info.fields, a, b)
+                } else if let Some(enum_info) = self.enums.get(path) {
+                    match (variant_of(enum_info, a), variant_of(enum_info, b)) {
+                        (Ok((a_index, variant, a)), Ok((b_index, _, b))) => a_index
+                            .cmp(&b_index)
+                            .then_with(|| self.compare_variants(variant, a, b)),
+                        _ => Ordering::Equal,
+                    }
+                } else {
+                    Ordering::Equal
+                }
+            }
+            TypeKind::Vec(inner) | TypeKind::Array(inner, _) => {
+                match (a.as_array(), b.as_array()) {
+                    (Some(a), Some(b)) => a
+                        .iter()
+                        .zip(b)
+                        .map(|(a, b)| self.compare_keys(inner, a, b))
+                        .find(|ordering| ordering.is_ne())
+                        .unwrap_or_else(|| a.len().cmp(&b.len())),
+                    _ => Ordering::Equal,
+                }
+            }
+            // `None` comes first
+            TypeKind::Option(inner) => match (a.is_null(), b.is_null()) {
+                (false, false) => self.compare_keys(inner, a, b),
+                (a_null, b_null) => b_null.cmp(&a_null),
+            },
+            // Maps aren't ordered in Rust, so they can't be keys
+            TypeKind::HashMap(..) => Ordering::Equal,
+        }
+    }
+
+    fn compare_fields(&self, fields: &[FieldInfo], a: &Value, b: &Value) -> Ordering {
+        fields
+            .iter()
+            .map(|field| match (a.get(&field.name), b.get(&field.name)) {
+                (Some(a), Some(b)) => self.compare_keys(&field.type_kind, a, b),
+                _ => Ordering::Equal,
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// Compares the payloads of two values of the same variant
+    fn compare_variants(&self, variant: &EnumVariant, a: &Value, b: &Value) -> Ordering {
+        match &variant.fields {
+            None => Ordering::Equal,
+            Some(fields) if is_tuple(fields) && fields.len() == 1 => {
+                self.compare_keys(&fields[0].type_kind, a, b)
+            }
+            Some(fields) if is_tuple(fields) => match (a.as_array(), b.as_array()) {
+                (Some(a), Some(b)) => fields
+                    .iter()
+                    .zip(a.iter().zip(b))
+                    .map(|(field, (a, b))| self.compare_keys(&field.type_kind, a, b))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal),
+                _ => Ordering::Equal,
+            },
+            Some(fields) => self.compare_fields(fields, a, b),
+        }
+    }
+}
+
+/// The index, variant and payload of an enum value. Unit variants may also be given
+/// as a plain string.
+fn variant_of<'e, 'v>(
+    enum_info: &'e EnumInfo,
+    value: &'v Value,
+) -> Result<(usize, &'e EnumVariant, &'v Value)> {
+    let (name, payload) = match value {
+        Value::String(name) => (name, &Value::Null),
+        Value::Object(object) if object.len() == 1 => object.iter().next().unwrap(),
+        _ => bail!(
+            "Expected a single-key object naming a variant, found {}",
+            value
+        ),
+    };
+    let (index, variant) = enum_info
+        .variants
+        .iter()
+        .enumerate()
+        .find(|(_, variant)| variant.name == *name)
+        .ok_or_else(|| anyhow!("Unknown variant `{}`", name))?;
+    Ok((index, variant, payload))
+}
+
+/// Reads through a Borsh buffer, failing instead of running past its end
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn read(&mut self, len: usize) -> Result<&[u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unexpected end of input: needed {} bytes at offset {}",
+                    len,
+                    self.offset
+                )
+            })?;
+        let bytes = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.read(N)?.try_into().unwrap())
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+}
+
+fn decode_primitive(name: &str, reader: &mut Reader) -> Result<Value> {
+    let value = match name {
+        "bool" => match reader.read(1)?[0] {
+            0 => Value::Bool(false),
+            1 => Value::Bool(true),
+            byte => bail!("Invalid bool {}", byte),
+        },
+        "u8" => u8::from_le_bytes(reader.read_array()?).into(),
+        "u16" => u16::from_le_bytes(reader.read_array()?).into(),
+        "u32" => u32::from_le_bytes(reader.read_array()?).into(),
+        "i8" => i8::from_le_bytes(reader.read_array()?).into(),
+        "i16" => i16::from_le_bytes(reader.read_array()?).into(),
+        "i32" => i32::from_le_bytes(reader.read_array()?).into(),
+        "u64" => u64::from_le_bytes(reader.read_array()?).to_string().into(),
+        "i64" => i64::from_le_bytes(reader.read_array()?).to_string().into(),
+        "u128" => u128::from_le_bytes(reader.read_array()?).to_string().into(),
+        "i128" => i128::from_le_bytes(reader.read_array()?).to_string().into(),
+        "f32" => float(f32::from_le_bytes(reader.read_array()?) as f64)?,
+        "f64" => float(f64::from_le_bytes(reader.read_array()?))?,
+        _ => bail!("Unsupported primitive `{}`", name),
+    };
+    Ok(value)
+}
+
+/// Borsh only rejects NaN, so infinities are written as strings
+fn float(value: f64) -> Result<Value> {
+    if value.is_nan() {
+        bail!("Invalid float NaN");
+    }
+    Ok(match Number::from_f64(value) {
+        Some(number) => Value::Number(number),
+        None if value > 0.0 => Value::String("Infinity".to_string()),
+        None => Value::String("-Infinity".to_string()),
+    })
+}
+
+/// A float given as a JSON number, `"Infinity"` or `"-Infinity"`
+fn float_of(value: &Value) -> Result<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) if text == "Infinity" => Some(f64::INFINITY),
+        Value::String(text) if text == "-Infinity" => Some(f64::NEG_INFINITY),
+        _ => None,
+    }
+    .ok_or_else(|| anyhow!("Expected a number, found {}", value))
+}
+
+fn encode_primitive(name: &str, value: &Value, output: &mut Vec<u8>) -> Result<()> {
+    match name {
+        "bool" => {
+            let value = value
+                .as_bool()
+                .ok_or_else(|| anyhow!("Expected a boolean, found {}", value))?;
+            output.push(value as u8);
+        }
+        "u8" => output.extend(u8::try_from(integer::<u128>(value)?)?.to_le_bytes()),
+        "u16" => output.extend(u16::try_from(integer::<u128>(value)?)?.to_le_bytes()),
+        "u32" => output.extend(u32::try_from(integer::<u128>(value)?)?.to_le_bytes()),
+        "u64" => output.extend(u64::try_from(integer::<u128>(value)?)?.to_le_bytes()),
+        "u128" => output.extend(integer::<u128>(value)?.to_le_bytes()),
+        "i8" => output.extend(i8::try_from(integer::<i128>(value)?)?.to_le_bytes()),
+        "i16" => output.extend(i16::try_from(integer::<i128>(value)?)?.to_le_bytes()),
+        "i32" => output.extend(i32::try_from(integer::<i128>(value)?)?.to_le_bytes()),
+        "i64" => output.extend(i64::try_from(integer::<i128>(value)?)?.to_le_bytes()),
+        "i128" => output.extend(integer::<i128>(value)?.to_le_bytes()),
+        "f32" | "f64" => {
+            let number = float_of(value)?;
+            if name == "f32" {
+                output.extend((number as f32).to_le_bytes());
+            } else {
+                output.extend(number.to_le_bytes());
+            }
+        }
+        _ => bail!("Unsupported primitive `{}`", name),
+    }
+    Ok(())
+}
+
+/// An integer given as a JSON number or a decimal string
+fn integer<T: std::str::FromStr>(value: &Value) -> Result<T> {
+    let text = match value {
+        Value::Number(number) => number.to_string(),
+        Value::String(text) => text.clone(),
+        _ => bail!("Expected an integer, found {}", value),
+    };
+    text.parse()
+        .map_err(|_| anyhow!("Expected an integer, found {}", value))
+}
+
+fn write_len(len: usize, output: &mut Vec<u8>) -> Result<()> {
+    let len = u32::try_from(len).context("Too many elements for a u32 length")?;
+    output.extend(len.to_le_bytes());
+    Ok(())
+}
+
+/// Unsigned integers are compared as `u128`, so values above `i128::MAX` keep their order
+fn compare_primitives(name: &str, a: &Value, b: &Value) -> Ordering {
+    match name {
+        "bool" => a.as_bool().cmp(&b.as_bool()),
+        "f32" | "f64" => float_of(a)
+            .ok()
+            .partial_cmp(&float_of(b).ok())
+            .unwrap_or(Ordering::Equal),
+        _ if name.starts_with('u') => integer::<u128>(a).ok().cmp(&integer::<u128>(b).ok()),
+        _ => integer::<i128>(a).ok().cmp(&integer::<i128>(b).ok()),
+    }
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Parses hex digits, ignoring whitespace and an optional `0x` prefix
+pub fn from_hex(hex: &str) -> Result<Vec<u8>> {
+    let digits: String = hex.split_whitespace().collect();
+    let digits = digits.strip_prefix("0x").unwrap_or(&digits);
+    if !digits.is_ascii() {
+        bail!("Invalid hex {}", hex);
+    }
+    if !digits.len().is_multiple_of(2) {
+        bail!("Odd number of hex digits");
+    }
+
+    (0..digits.len())
+        .step_by(2)
+        .map(|index| {
+            u8::from_str_radix(&digits[index..index + 2], 16)
+                .with_context(|| format!("Invalid hex at offset {}", index))
+        })
+        .collect()
+}
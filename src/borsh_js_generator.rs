@@ -0,0 +1,209 @@
+use super::dependency_resolver::TypeDependencies;
+use super::type_parser::{EnumInfo, FieldInfo, StructInfo, TypeKind};
+use crate::backend::{
+    module_type_names, typescript_barrels, typescript_import_specifier, typescript_module_file,
+    Backend,
+};
+use crate::Config;
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+
+/// Generates schema objects for the `borsh` npm package
+pub struct BorshJsGenerator {
+    structs: HashMap<String, StructInfo>,
+    enums: HashMap<String, EnumInfo>,
+    config: Config,
+}
+
+impl BorshJsGenerator {
+    pub fn new(structs: HashMap<String, StructInfo>, enums: HashMap<String, EnumInfo>) -> Self {
+        Self {
+            structs,
+            enums,
+            config: Config::default(),
+        }
+    }
+
+    /// Use the given configuration instead of the defaults
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+}
+
+impl Backend for BorshJsGenerator {
+    fn generate_module(
+        &self,
+        current_module: &str,
+        dependencies: &TypeDependencies,
+    ) -> Result<String> {
+        let mut output = String::new();
+
+        // Add base import
+        output.push_str("import type { Schema } from 'borsh';\n");
+
+        // Add imports from other modules
+        for (module_path, type_names) in &dependencies.module_imports {
+            if module_path != current_module {
+                let type_names: Vec<_> = type_names.iter().cloned().collect();
+                output.push_str(&self.render_import(current_module, module_path, &type_names));
+            }
+        }
+        output.push('\n');
+
+        // Generate schemas in dependency order, as each one references the ones before it
+        for type_path in &dependencies.ordered_types {
+            if let Some(struct_info) = self.structs.get(type_path) {
+                if struct_info.module_path == current_module {
+                    output.push_str(&self.generate_struct(struct_info));
+                    output.push_str("\n\n");
+                }
+            } else if let Some(enum_info) = self.enums.get(type_path) {
+                if enum_info.module_path == current_module {
+                    output.push_str(&self.generate_enum(enum_info));
+                    output.push_str("\n\n");
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn module_file(&self, module: &str) -> PathBuf {
+        typescript_module_file(module, &self.config)
+    }
+
+    fn render_import(&self, module: &str, from_module: &str, type_names: &[String]) -> String {
+        let schema_names: Vec<_> = type_names
+            .iter()
+            .map(|name| format!("{}Schema", name))
+            .collect();
+
+        format!(
+            "import {{ {} }} from '{}';\n",
+            schema_names.join(", "),
+            typescript_import_specifier(
+                &self.module_file(module),
+                &self.module_file(from_module),
+                &self.config
+            )
+        )
+    }
+
+    fn generate_files(
+        &self,
+        modules: &BTreeSet<String>,
+        dependencies: &TypeDependencies,
+    ) -> Result<BTreeMap<PathBuf, String>> {
+        let mut files = BTreeMap::new();
+        let mut exports = BTreeMap::new();
+        for module in modules {
+            files.insert(
+                self.module_file(module),
+                self.generate_module(module, dependencies)?,
+            );
+
+            let names = module_type_names(module, &self.structs, &self.enums, dependencies)
+                .into_iter()
+                .map(|name| format!("{}Schema", name))
+                .collect();
+            exports.insert(self.module_file(module), names);
+        }
+
+        if self.config.barrels {
+            files.extend(typescript_barrels(&exports, &self.config)?);
+        }
+        Ok(files)
+    }
+}
+
+impl BorshJsGenerator {
+    fn generate_struct(&self, struct_info: &StructInfo) -> String {
+        format!(
+            "export const {}Schema: Schema = {{\n    struct: {}\n}};",
+            struct_info.name,
+            self.fields_to_borsh(&struct_info.fields, 1)
+        )
+    }
+
+    fn generate_enum(&self, enum_info: &EnumInfo) -> String {
+        let mut variants = Vec::new();
+
+        for variant in &enum_info.variants {
+            let variant_schema = match &variant.fields {
+                None => "{ struct: {} }".to_string(),
+                Some(fields) if fields.len() == 1 && fields[0].name.is_empty() => {
+                    // Tuple variant with single field
+                    self.type_to_borsh(&fields[0].type_kind)
+                }
+                Some(fields) => {
+                    // borsh-js has no tuples, but positional struct keys encode the same way
+                    format!("{{ struct: {} }}", self.fields_to_borsh(fields, 2))
+                }
+            };
+
+            variants.push(format!(
+                "        {{ struct: {{ {}: {} }} }}",
+                variant.name, variant_schema
+            ));
+        }
+
+        format!(
+            "export const {}Schema: Schema = {{\n    enum: [\n{}\n    ]\n}};",
+            enum_info.name,
+            variants.join(",\n")
+        )
+    }
+
+    /// Renders the `{ field: schema }` object of a struct, indented by `depth` levels
+    fn fields_to_borsh(&self, fields: &[FieldInfo], depth: usize) -> String {
+        if fields.is_empty() {
+            return "{}".to_string();
+        }
+
+        let indent = "    ".repeat(depth);
+        let fields: Vec<_> = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let name = if field.name.is_empty() {
+                    format!("_{}", index)
+                } else {
+                    field.name.clone()
+                };
+                format!(
+                    "{}    {}: {}",
+                    indent,
+                    name,
+                    self.type_to_borsh(&field.type_kind)
+                )
+            })
+            .collect();
+
+        format!("{{\n{}\n{}}}", fields.join(",\n"), indent)
+    }
+
+    #[allow(clippy::only_used_in_recursion)] // Parameter is essential for recursive calls
+    fn type_to_borsh(&self, type_kind: &TypeKind) -> String {
+        match type_kind {
+            TypeKind::Primitive(name) => format!("'{}'", name),
+            TypeKind::String => "'string'".to_string(),
+            TypeKind::Struct(name, _) | TypeKind::Enum(name, _) => format!("{}Schema", name),
+            TypeKind::Vec(inner) => {
+                format!("{{ array: {{ type: {} }} }}", self.type_to_borsh(inner))
+            }
+            TypeKind::HashMap(key, value) => format!(
+                "{{ map: {{ key: {}, value: {} }} }}",
+                self.type_to_borsh(key),
+                self.type_to_borsh(value)
+            ),
+            TypeKind::Option(inner) => format!("{{ option: {} }}", self.type_to_borsh(inner)),
+            TypeKind::Array(inner, size) => format!(
+                "{{ array: {{ type: {}, len: {} }} }}",
+                self.type_to_borsh(inner),
+                size
+            ),
+        }
+    }
+}
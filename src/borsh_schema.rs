@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// JSON form of the `borsh` crate's `BorshSchemaContainer`: a root declaration
+/// and the definitions of every type it reaches
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BorshSchemaContainer {
+    pub declaration: String,
+    pub definitions: BTreeMap<String, Definition>,
+}
+
+/// How a declared type is laid out on the wire, mirroring `borsh::schema::Definition`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Definition {
+    /// Fixed-size value of the given number of bytes
+    Primitive(u8),
+    /// Repeated elements with a `length_width`-byte length prefix (0 for fixed length)
+    Sequence {
+        length_width: u8,
+        length_range: LengthRange,
+        elements: String,
+    },
+    Tuple {
+        elements: Vec<String>,
+    },
+    /// Variants as `(discriminant, name, declaration)`
+    Enum {
+        tag_width: u8,
+        variants: Vec<(i64, String, String)>,
+    },
+    Struct {
+        fields: Fields,
+    },
+}
+
+/// Allowed sequence lengths, inclusive on both ends
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LengthRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Fields {
+    NamedFields(Vec<(String, String)>),
+    UnnamedFields(Vec<String>),
+    Empty,
+}
+
+/// Length prefix width of dynamically sized sequences
+pub const DEFAULT_LENGTH_WIDTH: u8 = 4;
+
+/// Tag width of enums
+pub const DEFAULT_TAG_WIDTH: u8 = 1;
+
+/// Declaration of the unit type, used by `Option`'s `None` variant
+pub const UNIT_DECLARATION: &str = "()";
+
+impl LengthRange {
+    /// Any length a `u32` prefix can express
+    pub fn dynamic() -> Self {
+        Self {
+            start: 0,
+            end: u32::MAX as u64,
+        }
+    }
+
+    /// Exactly `len` elements
+    pub fn fixed(len: u64) -> Self {
+        Self {
+            start: len,
+            end: len,
+        }
+    }
+}
+
+/// Size in bytes of a primitive type, or `None` if `name` isn't one
+pub fn primitive_size(name: &str) -> Option<u8> {
+    match name {
+        "()" => Some(0),
+        "u8" | "i8" | "bool" => Some(1),
+        "u16" | "i16" => Some(2),
+        "u32" | "i32" | "f32" => Some(4),
+        "u64" | "i64" | "f64" => Some(8),
+        "u128" | "i128" => Some(16),
+        _ => None,
+    }
+}
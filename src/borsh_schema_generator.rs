@@ -0,0 +1,293 @@
+use super::dependency_resolver::TypeDependencies;
+use super::type_parser::{EnumInfo, FieldInfo, StructInfo, TypeKind};
+use crate::backend::Backend;
+use crate::borsh_schema::{
+    primitive_size, BorshSchemaContainer, Definition, Fields, LengthRange, DEFAULT_LENGTH_WIDTH,
+    DEFAULT_TAG_WIDTH, UNIT_DECLARATION,
+};
+use crate::{Config, OutputStructure};
+use anyhow::Result;
+use serde_json::Map;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+type Definitions = BTreeMap<String, Definition>;
+
+/// Generates `BorshSchemaContainer` JSON, as the `BorshSchema` derive would produce at runtime.
+///
+/// Each module's file maps its type names to one container per type.
+pub struct BorshSchemaGenerator {
+    structs: HashMap<String, StructInfo>,
+    enums: HashMap<String, EnumInfo>,
+    config: Config,
+}
+
+impl BorshSchemaGenerator {
+    pub fn new(structs: HashMap<String, StructInfo>, enums: HashMap<String, EnumInfo>) -> Self {
+        Self {
+            structs,
+            enums,
+            config: Config::default(),
+        }
+    }
+
+    /// Use the given configuration instead of the defaults
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Container for the type at `type_path`, or `None` if it isn't known
+    pub fn container(&self, type_path: &str) -> Option<BorshSchemaContainer> {
+        let type_kind = if let Some(struct_info) = self.structs.get(type_path) {
+            TypeKind::Struct(struct_info.name.clone(), type_path.to_string())
+        } else {
+            let enum_info = self.enums.get(type_path)?;
+            TypeKind::Enum(enum_info.name.clone(), type_path.to_string())
+        };
+
+        let mut definitions = Definitions::new();
+        let declaration = self.add_definitions(&type_kind, &mut definitions);
+
+        Some(BorshSchemaContainer {
+            declaration,
+            definitions,
+        })
+    }
+}
+
+impl Backend for BorshSchemaGenerator {
+    fn generate_module(
+        &self,
+        current_module: &str,
+        dependencies: &TypeDependencies,
+    ) -> Result<String> {
+        let mut containers = Map::new();
+
+        for type_path in &dependencies.ordered_types {
+            let name = if let Some(struct_info) = self.structs.get(type_path) {
+                (struct_info.module_path == current_module).then_some(&struct_info.name)
+            } else if let Some(enum_info) = self.enums.get(type_path) {
+                (enum_info.module_path == current_module).then_some(&enum_info.name)
+            } else {
+                None
+            };
+
+            if let (Some(name), Some(container)) = (name, self.container(type_path)) {
+                containers.insert(name.clone(), serde_json::to_value(container)?);
+            }
+        }
+
+        Ok(serde_json::to_string_pretty(&containers)? + "\n")
+    }
+
+    fn module_file(&self, module: &str) -> PathBuf {
+        let separator = match self.config.output_structure {
+            OutputStructure::Nested => "/",
+            OutputStructure::Flat => "_",
+        };
+        PathBuf::from(
+            format!("{}.borsh-schema.json", module.replace("::", separator)).to_lowercase(),
+        )
+    }
+
+    /// Containers are self-contained, so modules never import each other
+    fn render_import(&self, _module: &str, _from_module: &str, _type_names: &[String]) -> String {
+        String::new()
+    }
+}
+
+impl BorshSchemaGenerator {
+    /// Declarations are bare type names, like the derive's, unless two types share a
+    /// name; those are declared by full path so their definitions don't clash
+    fn declaration(&self, name: &str, type_path: &str) -> String {
+        let same_name = self
+            .structs
+            .values()
+            .map(|s| &s.name)
+            .chain(self.enums.values().map(|e| &e.name))
+            .filter(|other| *other == name)
+            .count();
+
+        if same_name > 1 {
+            type_path.to_string()
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Adds the definition of `type_kind` and everything it references, returning its declaration
+    fn add_definitions(&self, type_kind: &TypeKind, definitions: &mut Definitions) -> String {
+        match type_kind {
+            TypeKind::Primitive(name) => {
+                add_primitive(name, definitions);
+                name.clone()
+            }
+            TypeKind::String => {
+                add_primitive("u8", definitions);
+                definitions.insert(
+                    "String".to_string(),
+                    Definition::Sequence {
+                        length_width: DEFAULT_LENGTH_WIDTH,
+                        length_range: LengthRange::dynamic(),
+                        elements: "u8".to_string(),
+                    },
+                );
+                "String".to_string()
+            }
+            TypeKind::Struct(name, path) | TypeKind::Enum(name, path) => {
+                let declaration = self.declaration(name, path);
+                if definitions.contains_key(&declaration) {
+                    return declaration;
+                }
+
+                if let Some(struct_info) = self.structs.get(path) {
+                    // Reserve the declaration first so self-references terminate
+                    definitions.insert(
+                        declaration.clone(),
+                        Definition::Struct {
+                            fields: Fields::Empty,
+                        },
+                    );
+                    let fields = self.fields_definition(&struct_info.fields, definitions);
+                    definitions.insert(declaration.clone(), Definition::Struct { fields });
+                } else if let Some(enum_info) = self.enums.get(path) {
+                    definitions.insert(
+                        declaration.clone(),
+                        Definition::Enum {
+                            tag_width: DEFAULT_TAG_WIDTH,
+                            variants: Vec::new(),
+                        },
+                    );
+                    let variants = self.enum_variants(&declaration, enum_info, definitions);
+                    definitions.insert(
+                        declaration.clone(),
+                        Definition::Enum {
+                            tag_width: DEFAULT_TAG_WIDTH,
+                            variants,
+                        },
+                    );
+                }
+                declaration
+            }
+            TypeKind::Vec(inner) => {
+                let elements = self.add_definitions(inner, definitions);
+                let declaration = format!("Vec<{}>", elements);
+                definitions.insert(
+                    declaration.clone(),
+                    Definition::Sequence {
+                        length_width: DEFAULT_LENGTH_WIDTH,
+                        length_range: LengthRange::dynamic(),
+                        elements,
+                    },
+                );
+                declaration
+            }
+            TypeKind::HashMap(key, value) => {
+                let key = self.add_definitions(key, definitions);
+                let value = self.add_definitions(value, definitions);
+                let entry = format!("({}, {})", key, value);
+                definitions.insert(
+                    entry.clone(),
+                    Definition::Tuple {
+                        elements: vec![key.clone(), value.clone()],
+                    },
+                );
+
+                let declaration = format!("HashMap<{}, {}>", key, value);
+                definitions.insert(
+                    declaration.clone(),
+                    Definition::Sequence {
+                        length_width: DEFAULT_LENGTH_WIDTH,
+                        length_range: LengthRange::dynamic(),
+                        elements: entry,
+                    },
+                );
+                declaration
+            }
+            TypeKind::Option(inner) => {
+                let inner = self.add_definitions(inner, definitions);
+                add_primitive(UNIT_DECLARATION, definitions);
+
+                let declaration = format!("Option<{}>", inner);
+                definitions.insert(
+                    declaration.clone(),
+                    Definition::Enum {
+                        tag_width: DEFAULT_TAG_WIDTH,
+                        variants: vec![
+                            (0, "None".to_string(), UNIT_DECLARATION.to_string()),
+                            (1, "Some".to_string(), inner),
+                        ],
+                    },
+                );
+                declaration
+            }
+            TypeKind::Array(inner, size) => {
+                let elements = self.add_definitions(inner, definitions);
+                let declaration = format!("[{}; {}]", elements, size);
+                definitions.insert(
+                    declaration.clone(),
+                    Definition::Sequence {
+                        length_width: 0,
+                        length_range: LengthRange::fixed(*size as u64),
+                        elements,
+                    },
+                );
+                declaration
+            }
+        }
+    }
+
+    /// Each variant is declared as a struct named after the enum and the variant
+    fn enum_variants(
+        &self,
+        enum_declaration: &str,
+        enum_info: &EnumInfo,
+        definitions: &mut Definitions,
+    ) -> Vec<(i64, String, String)> {
+        let mut variants = Vec::new();
+
+        for (discriminant, variant) in enum_info.variants.iter().enumerate() {
+            let fields = match &variant.fields {
+                None => Fields::Empty,
+                Some(fields) => self.fields_definition(fields, definitions),
+            };
+            let declaration = format!("{}{}", enum_declaration, variant.name);
+            definitions.insert(declaration.clone(), Definition::Struct { fields });
+            variants.push((discriminant as i64, variant.name.clone(), declaration));
+        }
+
+        variants
+    }
+
+    fn fields_definition(&self, fields: &[FieldInfo], definitions: &mut Definitions) -> Fields {
+        if fields.is_empty() {
+            Fields::Empty
+        } else if fields.iter().all(|field| field.name.is_empty()) {
+            Fields::UnnamedFields(
+                fields
+                    .iter()
+                    .map(|field| self.add_definitions(&field.type_kind, definitions))
+                    .collect(),
+            )
+        } else {
+            Fields::NamedFields(
+                fields
+                    .iter()
+                    .map(|field| {
+                        (
+                            field.name.clone(),
+                            self.add_definitions(&field.type_kind, definitions),
+                        )
+                    })
+                    .collect(),
+            )
+        }
+    }
+}
+
+fn add_primitive(name: &str, definitions: &mut Definitions) {
+    if let Some(size) = primitive_size(name) {
+        definitions.insert(name.to_string(), Definition::Primitive(size));
+    }
+}
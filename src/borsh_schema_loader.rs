@@ -0,0 +1,314 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::borsh_schema::{primitive_size, BorshSchemaContainer, Definition, Fields};
+use crate::type_parser::{Docs, EnumInfo, EnumVariant, FieldInfo, StructInfo, TypeKind};
+
+/// A single container, or the map of containers the borsh-schema backend writes per module
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ContainerFile {
+    Single(BorshSchemaContainer),
+    Many(BTreeMap<String, BorshSchemaContainer>),
+}
+
+/// Converts `BorshSchemaContainer` JSON into the types `TypeParser` produces, placing
+/// every type it finds in one module
+pub struct BorshSchemaLoader {
+    module_path: String,
+    pub structs: HashMap<String, StructInfo>,
+    pub enums: HashMap<String, EnumInfo>,
+    /// The declaration each generated type path came from
+    declarations: HashMap<String, String>,
+}
+
+impl BorshSchemaLoader {
+    pub fn new(module_path: String) -> Self {
+        Self {
+            module_path,
+            structs: HashMap::new(),
+            enums: HashMap::new(),
+            declarations: HashMap::new(),
+        }
+    }
+
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        self.load_json(&json)
+            .with_context(|| format!("Invalid borsh schema file: {}", path.display()))
+    }
+
+    /// Loads a single container, or a map of type names to containers
+    pub fn load_json(&mut self, json: &str) -> Result<()> {
+        let file: ContainerFile =
+            serde_json::from_str(json).context("Failed to parse borsh schema container")?;
+
+        match file {
+            ContainerFile::Single(container) => self.add_container(&container),
+            ContainerFile::Many(containers) => {
+                for container in containers.values() {
+                    self.add_container(container)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Adds the container's root type and every struct and enum it reaches
+    pub fn add_container(&mut self, container: &BorshSchemaContainer) -> Result<()> {
+        match self.load_type(container)? {
+            TypeKind::Struct(..) | TypeKind::Enum(..) => Ok(()),
+            _ => bail!(
+                "`{}` is not a struct or enum, so there is nothing to generate",
+                container.declaration
+            ),
+        }
+    }
+
+    /// Adds every struct and enum the container reaches, returning the root's type,
+    /// which may be a primitive or collection
+    pub fn load_type(&mut self, container: &BorshSchemaContainer) -> Result<TypeKind> {
+        self.type_kind(&container.declaration, &container.definitions)
+    }
+
+    fn type_kind(
+        &mut self,
+        declaration: &str,
+        definitions: &BTreeMap<String, Definition>,
+    ) -> Result<TypeKind> {
+        if declaration == "String" || declaration == "str" {
+            return Ok(TypeKind::String);
+        }
+        if declaration != "()" && primitive_size(declaration).is_some() {
+            return Ok(TypeKind::Primitive(declaration.to_string()));
+        }
+
+        let definition = definitions
+            .get(declaration)
+            .ok_or_else(|| anyhow!("No definition for `{}`", declaration))?;
+
+        match definition {
+            Definition::Primitive(_) => bail!("Unsupported primitive `{}`", declaration),
+            Definition::Sequence {
+                length_width: 0,
+                length_range,
+                elements,
+            } if length_range.start == length_range.end => Ok(TypeKind::Array(
+                Box::new(self.type_kind(elements, definitions)?),
+                length_range.start as usize,
+            )),
+            Definition::Sequence {
+                length_width,
+                elements,
+                ..
+            } => {
+                // Everything else is generated with a u32 length prefix
+                if *length_width != 4 {
+                    bail!(
+                        "Unsupported length width {} in `{}`",
+                        length_width,
+                        declaration
+                    );
+                }
+                if declaration.starts_with("HashMap<") || declaration.starts_with("BTreeMap<") {
+                    if let Some(Definition::Tuple { elements: entry }) = definitions.get(elements) {
+                        if let [key, value] = entry.as_slice() {
+                            return Ok(TypeKind::HashMap(
+                                Box::new(self.type_kind(key, definitions)?),
+                                Box::new(self.type_kind(value, definitions)?),
+                            ));
+                        }
+                    }
+                }
+                // Sets encode like vectors
+                Ok(TypeKind::Vec(Box::new(
+                    self.type_kind(elements, definitions)?,
+                )))
+            }
+            Definition::Enum { variants, .. } if is_option(declaration, variants) => Ok(
+                TypeKind::Option(Box::new(self.type_kind(&variants[1].2, definitions)?)),
+            ),
+            Definition::Tuple { .. } => bail!("Tuples are not supported: `{}`", declaration),
+            Definition::Struct { fields } => {
+                let (name, path) = self.type_path(declaration)?;
+                if self.structs.contains_key(&path) {
+                    return Ok(TypeKind::Struct(name, path));
+                }
+
+                // Reserve the path first so self-references terminate
+                let mut struct_info = StructInfo {
+                    name: name.clone(),
+                    module_path: self.module_path.clone(),
+                    fields: Vec::new(),
+                    docs: Docs::default(),
+                };
+                self.structs.insert(path.clone(), struct_info.clone());
+                struct_info.fields = match fields {
+                    Fields::NamedFields(fields) => self.named_fields(fields, definitions)?,
+                    // Positional fields encode the same as named ones in order
+                    Fields::UnnamedFields(fields) => fields
+                        .iter()
+                        .enumerate()
+                        .map(|(index, field)| {
+                            Ok(FieldInfo {
+                                name: format!("_{}", index),
+                                type_kind: self.type_kind(field, definitions)?,
+                                docs: Docs::default(),
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                    Fields::Empty => Vec::new(),
+                };
+                self.structs.insert(path.clone(), struct_info);
+
+                Ok(TypeKind::Struct(name, path))
+            }
+            Definition::Enum {
+                tag_width,
+                variants,
+            } => {
+                if *tag_width != 1 {
+                    bail!("Unsupported tag width {} in `{}`", tag_width, declaration);
+                }
+
+                let (name, path) = self.type_path(declaration)?;
+                if self.enums.contains_key(&path) {
+                    return Ok(TypeKind::Enum(name, path));
+                }
+
+                let mut enum_info = EnumInfo {
+                    name: name.clone(),
+                    module_path: self.module_path.clone(),
+                    variants: Vec::new(),
+                    docs: Docs::default(),
+                };
+                self.enums.insert(path.clone(), enum_info.clone());
+                for (index, (discriminant, variant_name, variant_declaration)) in
+                    variants.iter().enumerate()
+                {
+                    if *discriminant != index as i64 {
+                        bail!(
+                            "Explicit discriminants are not supported: `{}::{}` = {}",
+                            declaration,
+                            variant_name,
+                            discriminant
+                        );
+                    }
+                    enum_info.variants.push(EnumVariant {
+                        name: variant_name.clone(),
+                        fields: self.variant_fields(variant_declaration, definitions)?,
+                        docs: Docs::default(),
+                    });
+                }
+                self.enums.insert(path.clone(), enum_info);
+
+                Ok(TypeKind::Enum(name, path))
+            }
+        }
+    }
+
+    /// Name and path for a struct or enum declaration. Declarations that differ only in
+    /// their module path would share one, so they are rejected
+    fn type_path(&mut self, declaration: &str) -> Result<(String, String)> {
+        let name = type_name(declaration);
+        let path = format!("{}::{}", self.module_path, name);
+        match self.declarations.get(&path) {
+            Some(existing) if existing != declaration => bail!(
+                "`{}` and `{}` would both generate `{}`",
+                existing,
+                declaration,
+                path
+            ),
+            Some(_) => {}
+            None => {
+                self.declarations
+                    .insert(path.clone(), declaration.to_string());
+            }
+        }
+        Ok((name, path))
+    }
+
+    /// Variants are declared as structs; their fields become the variant's own
+    fn variant_fields(
+        &mut self,
+        declaration: &str,
+        definitions: &BTreeMap<String, Definition>,
+    ) -> Result<Option<Vec<FieldInfo>>> {
+        match definitions.get(declaration) {
+            Some(Definition::Struct { fields }) => match fields {
+                Fields::NamedFields(fields) => Ok(Some(self.named_fields(fields, definitions)?)),
+                Fields::UnnamedFields(fields) => Ok(Some(
+                    fields
+                        .iter()
+                        .map(|field| {
+                            Ok(FieldInfo {
+                                name: String::new(),
+                                type_kind: self.type_kind(field, definitions)?,
+                                docs: Docs::default(),
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                )),
+                Fields::Empty => Ok(None),
+            },
+            // Any other declaration is the variant's single positional field
+            _ => Ok(Some(vec![FieldInfo {
+                name: String::new(),
+                type_kind: self.type_kind(declaration, definitions)?,
+                docs: Docs::default(),
+            }])),
+        }
+    }
+
+    fn named_fields(
+        &mut self,
+        fields: &[(String, String)],
+        definitions: &BTreeMap<String, Definition>,
+    ) -> Result<Vec<FieldInfo>> {
+        fields
+            .iter()
+            .map(|(name, declaration)| {
+                Ok(FieldInfo {
+                    name: name.clone(),
+                    type_kind: self.type_kind(declaration, definitions)?,
+                    docs: Docs::default(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn is_option(declaration: &str, variants: &[(i64, String, String)]) -> bool {
+    declaration.starts_with("Option<")
+        && variants.len() == 2
+        && variants[0].1 == "None"
+        && variants[1].1 == "Some"
+}
+
+/// Type name for a declaration: the last path segment, with generic arguments
+/// folded in (`Wrapper<u64>` becomes `WrapperU64`)
+fn type_name(declaration: &str) -> String {
+    let base_end = declaration.find('<').unwrap_or(declaration.len());
+    let base = declaration[..base_end]
+        .rsplit("::")
+        .next()
+        .unwrap_or_default();
+
+    let arguments = declaration[base_end..]
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        });
+
+    std::iter::once(base.to_string()).chain(arguments).collect()
+}
@@ -1,19 +1,40 @@
 use super::dependency_resolver::TypeDependencies;
-use super::type_parser::{EnumInfo, StructInfo, TypeKind};
-use anyhow::Result;
-use std::collections::HashMap;
+use super::type_parser::{Docs, EnumInfo, EnumVariant, FieldInfo, StructInfo, TypeKind};
+use crate::backend::{
+    module_type_names, typescript_barrels, typescript_extension, typescript_import_specifier,
+    typescript_module_file, Backend,
+};
+use crate::size::{Size, SizeAnalyzer};
+use crate::test_suites::TestSuiteGenerator;
+use crate::{Config, TestSuites, TupleVariants, TypeDeclarations};
+use anyhow::{bail, Result};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
 
 pub struct ZorshGenerator {
     structs: HashMap<String, StructInfo>,
     enums: HashMap<String, EnumInfo>,
+    config: Config,
 }
 
 impl ZorshGenerator {
     pub fn new(structs: HashMap<String, StructInfo>, enums: HashMap<String, EnumInfo>) -> Self {
-        Self { structs, enums }
+        Self {
+            structs,
+            enums,
+            config: Config::default(),
+        }
+    }
+
+    /// Use the given configuration instead of the defaults
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
     }
+}
 
-    pub fn generate_module(
+impl Backend for ZorshGenerator {
+    fn generate_module(
         &self,
         current_module: &str,
         dependencies: &TypeDependencies,
@@ -26,39 +47,126 @@ impl ZorshGenerator {
         // Add imports from other modules
         for (module_path, type_names) in &dependencies.module_imports {
             if module_path != current_module {
-                let schema_names: Vec<_> = type_names
+                // Overridden types are replaced inline and never imported
+                let imported: Vec<_> = type_names
                     .iter()
-                    .map(|name| format!("{}Schema", name))
+                    .filter(|name| {
+                        self.type_override(name, &format!("{}::{}", module_path, name))
+                            .is_none()
+                    })
+                    .cloned()
                     .collect();
+                if imported.is_empty() {
+                    continue;
+                }
 
+                output.push_str(&self.render_import(current_module, module_path, &imported));
+                // Interfaces refer to the other modules' types, not just their schemas
+                if self.config.type_declarations == TypeDeclarations::Interfaces {
+                    output.push_str(&format!(
+                        "import type {{ {} }} from '{}';\n",
+                        self.type_names(&imported).join(", "),
+                        self.import_specifier(current_module, module_path)
+                    ));
+                }
+            }
+        }
+        if self.config.type_declarations == TypeDeclarations::SeparateFile {
+            let type_names =
+                module_type_names(current_module, &self.structs, &self.enums, dependencies);
+            if !type_names.is_empty() {
+                let has_helpers = |name: &String| {
+                    self.config.enum_helpers
+                        && self
+                            .enums
+                            .contains_key(&format!("{}::{}", current_module, name))
+                };
+                // Enum helpers are values, so they can't be re-exported as types only
+                let exports = if type_names.iter().any(has_helpers) {
+                    let names: Vec<_> = type_names
+                        .iter()
+                        .map(|name| {
+                            let type_name = self.config.naming.type_name(name);
+                            if has_helpers(name) {
+                                type_name
+                            } else {
+                                format!("type {}", type_name)
+                            }
+                        })
+                        .collect();
+                    format!("{{ {} }}", names.join(", "))
+                } else {
+                    format!("type {{ {} }}", self.type_names(&type_names).join(", "))
+                };
                 output.push_str(&format!(
-                    "import {{ {} }} from './{}';\n",
-                    schema_names.join(", "),
-                    module_path.replace("::", "/").to_lowercase()
+                    "export {} from '{}';\n",
+                    exports,
+                    typescript_import_specifier(
+                        &self.module_file(current_module),
+                        &self.types_file(current_module),
+                        &self.config
+                    )
                 ));
             }
         }
         output.push('\n');
 
         // Generate type definitions in dependency order
+        let sizes = self.size_analyzer();
         for type_path in &dependencies.ordered_types {
             // Only generate types that belong to the current module
             let type_module = self.get_type_module(type_path);
             if type_module == current_module {
                 if let Some(struct_info) = self.structs.get(type_path) {
                     // Generate struct schema and type definition
-                    output.push_str(&self.generate_struct(struct_info));
-                    output.push_str(&format!(
-                        "\nexport type {} = b.infer<typeof {}Schema>;\n\n",
-                        struct_info.name, struct_info.name
+                    output.push_str(&jsdoc(&struct_info.docs, ""));
+                    output.push_str(&self.generate_struct(struct_info)?);
+                    output.push('\n');
+                    match self.config.type_declarations {
+                        TypeDeclarations::Infer => {
+                            output.push_str(&jsdoc(&struct_info.docs, ""));
+                            output.push_str(&self.infer_type(&struct_info.name));
+                        }
+                        TypeDeclarations::Interfaces => {
+                            output.push_str(&jsdoc(&struct_info.docs, ""));
+                            output.push_str(&self.generate_struct_type(struct_info));
+                        }
+                        TypeDeclarations::SeparateFile => {}
+                    }
+                    output.push_str(&self.generate_fixed_size(
+                        &sizes,
+                        type_path,
+                        &struct_info.name,
                     ));
+                    output.push('\n');
                 } else if let Some(enum_info) = self.enums.get(type_path) {
                     // Generate enum schema and type definition
-                    output.push_str(&self.generate_enum(enum_info));
-                    output.push_str(&format!(
-                        "\nexport type {} = b.infer<typeof {}Schema>;\n\n",
-                        enum_info.name, enum_info.name
-                    ));
+                    output.push_str(&jsdoc(&enum_info.docs, ""));
+                    output.push_str(&self.generate_enum(enum_info)?);
+                    output.push('\n');
+                    match self.config.type_declarations {
+                        TypeDeclarations::Infer => {
+                            output.push_str(&jsdoc(&enum_info.docs, ""));
+                            output.push_str(&self.infer_type(&enum_info.name));
+                        }
+                        TypeDeclarations::Interfaces => {
+                            output.push_str(&jsdoc(&enum_info.docs, ""));
+                            output.push_str(&self.generate_enum_type(enum_info));
+                        }
+                        TypeDeclarations::SeparateFile => {}
+                    }
+                    if self.config.enum_helpers
+                        && self.config.type_declarations != TypeDeclarations::SeparateFile
+                    {
+                        output.push('\n');
+                        output.push_str(&self.generate_enum_helpers(enum_info)?);
+                    }
+                    if self.config.enum_values && is_unit_only(enum_info) {
+                        output.push('\n');
+                        output.push_str(&self.generate_enum_values(enum_info));
+                    }
+                    output.push_str(&self.generate_fixed_size(&sizes, type_path, &enum_info.name));
+                    output.push('\n');
                 }
             }
         }
@@ -66,6 +174,88 @@ impl ZorshGenerator {
         Ok(output)
     }
 
+    fn module_file(&self, module: &str) -> PathBuf {
+        typescript_module_file(module, &self.config)
+    }
+
+    fn render_import(&self, module: &str, from_module: &str, type_names: &[String]) -> String {
+        let schema_names: Vec<_> = type_names
+            .iter()
+            .map(|name| self.config.naming.schema_name(name))
+            .collect();
+
+        format!(
+            "import {{ {} }} from '{}';\n",
+            schema_names.join(", "),
+            self.import_specifier(module, from_module)
+        )
+    }
+
+    fn generate_files(
+        &self,
+        modules: &BTreeSet<String>,
+        dependencies: &TypeDependencies,
+    ) -> Result<BTreeMap<PathBuf, String>> {
+        let mut files = BTreeMap::new();
+        let mut exports = BTreeMap::new();
+        let sizes = self.size_analyzer();
+        for module in modules {
+            files.insert(
+                self.module_file(module),
+                self.generate_module(module, dependencies)?,
+            );
+            if self.config.type_declarations == TypeDeclarations::SeparateFile {
+                files.insert(
+                    self.types_file(module),
+                    self.generate_types_module(module, dependencies)?,
+                );
+            }
+            if self.config.test_suites != TestSuites::None {
+                let type_paths: Vec<_> = dependencies
+                    .ordered_types
+                    .iter()
+                    .filter(|type_path| self.get_type_module(type_path) == *module)
+                    .cloned()
+                    .collect();
+                let test_file = self
+                    .module_file(module)
+                    .with_extension(format!("test.{}", typescript_extension(&self.config)));
+                let suite = TestSuiteGenerator::new(&self.structs, &self.enums, &self.config)
+                    .generate(&test_file, &self.module_file(module), &type_paths);
+                files.insert(test_file, suite);
+            }
+
+            // Every type comes with its schema, and the schema file exports both
+            let mut names = Vec::new();
+            for name in module_type_names(module, &self.structs, &self.enums, dependencies) {
+                names.push(self.config.naming.schema_name(&name));
+                names.push(self.config.naming.type_name(&name));
+                let unit_only = self
+                    .enums
+                    .get(&format!("{}::{}", module, name))
+                    .is_some_and(is_unit_only);
+                if self.config.enum_values && unit_only {
+                    names.push(self.values_name(&name));
+                    names.push(self.discriminants_name(&name));
+                }
+                if self
+                    .fixed_size(&sizes, &format!("{}::{}", module, name))
+                    .is_some()
+                {
+                    names.push(self.fixed_size_name(&name));
+                }
+            }
+            exports.insert(self.module_file(module), names);
+        }
+
+        if self.config.barrels {
+            files.extend(typescript_barrels(&exports, &self.config)?);
+        }
+        Ok(files)
+    }
+}
+
+impl ZorshGenerator {
     fn get_type_module(&self, type_path: &str) -> String {
         if let Some(struct_info) = self.structs.get(type_path) {
             struct_info.module_path.to_string()
@@ -80,25 +270,37 @@ impl ZorshGenerator {
         }
     }
 
-    fn generate_struct(&self, struct_info: &StructInfo) -> String {
+    fn generate_struct(&self, struct_info: &StructInfo) -> Result<String> {
+        self.config.naming.check_field_keys(
+            &struct_info.name,
+            struct_info.fields.iter().map(|field| field.name.as_str()),
+        )?;
         let mut fields = Vec::new();
 
         for field in &struct_info.fields {
             fields.push(format!(
-                "    {}: {}",
-                field.name,
+                "{}    {}: {}",
+                jsdoc(&field.docs, "    "),
+                self.config.naming.field_key(&field.name),
                 self.type_to_zorsh(&field.type_kind)
             ));
         }
 
-        format!(
-            "export const {}Schema = b.struct({{\n{}\n}});",
-            struct_info.name,
+        Ok(format!(
+            "export const {} = b.struct({{\n{}\n}});",
+            self.config.naming.schema_name(&struct_info.name),
             fields.join(",\n")
-        )
+        ))
     }
 
-    fn generate_enum(&self, enum_info: &EnumInfo) -> String {
+    fn generate_enum(&self, enum_info: &EnumInfo) -> Result<String> {
+        self.config.naming.check_variant_keys(
+            &enum_info.name,
+            enum_info
+                .variants
+                .iter()
+                .map(|variant| variant.name.as_str()),
+        )?;
         let mut variants = Vec::new();
 
         for variant in &enum_info.variants {
@@ -108,13 +310,29 @@ impl ZorshGenerator {
                     // Tuple variant with single field
                     self.type_to_zorsh(&fields[0].type_kind)
                 }
+                Some(fields)
+                    if is_tuple(fields) && self.config.tuple_variants == TupleVariants::Tuple =>
+                {
+                    let elements: Vec<_> = fields
+                        .iter()
+                        .map(|field| self.type_to_zorsh(&field.type_kind))
+                        .collect();
+                    format!("b.tuple({})", elements.join(", "))
+                }
                 Some(fields) => {
-                    // Struct variant
+                    // Struct variant, or tuple variant with positional keys
+                    if !is_tuple(fields) {
+                        self.config.naming.check_field_keys(
+                            &format!("{}::{}", enum_info.name, variant.name),
+                            fields.iter().map(|field| field.name.as_str()),
+                        )?;
+                    }
                     let mut struct_fields = Vec::new();
-                    for field in fields {
+                    for (index, field) in fields.iter().enumerate() {
                         struct_fields.push(format!(
-                            "        {}: {}",
-                            field.name,
+                            "{}        {}: {}",
+                            jsdoc(&field.docs, "        "),
+                            self.variant_field_key(&field.name, index),
                             self.type_to_zorsh(&field.type_kind)
                         ));
                     }
@@ -122,23 +340,399 @@ impl ZorshGenerator {
                 }
             };
 
-            variants.push(format!("    {}: {}", variant.name, variant_schema));
+            variants.push(format!(
+                "{}    {}: {}",
+                jsdoc(&variant.docs, "    "),
+                self.config.naming.variant_key(&variant.name),
+                variant_schema
+            ));
         }
 
-        format!(
-            "export const {}Schema = b.enum({{\n{}\n}});",
-            enum_info.name,
+        Ok(format!(
+            "export const {} = b.enum({{\n{}\n}});",
+            self.config.naming.schema_name(&enum_info.name),
             variants.join(",\n")
+        ))
+    }
+
+    /// Contents of a module's `.types.ts` file, which doesn't depend on `@zorsh/zorsh`
+    fn generate_types_module(
+        &self,
+        current_module: &str,
+        dependencies: &TypeDependencies,
+    ) -> Result<String> {
+        let mut output = String::new();
+
+        for (module_path, type_names) in &dependencies.module_imports {
+            if module_path != current_module {
+                let imported: Vec<_> = type_names
+                    .iter()
+                    .filter(|name| {
+                        self.type_override(name, &format!("{}::{}", module_path, name))
+                            .is_none()
+                    })
+                    .cloned()
+                    .collect();
+                if !imported.is_empty() {
+                    output.push_str(&format!(
+                        "import type {{ {} }} from '{}';\n",
+                        self.type_names(&imported).join(", "),
+                        typescript_import_specifier(
+                            &self.types_file(current_module),
+                            &self.types_file(module_path),
+                            &self.config
+                        )
+                    ));
+                }
+            }
+        }
+        if !output.is_empty() {
+            output.push('\n');
+        }
+
+        for type_path in &dependencies.ordered_types {
+            if let Some(struct_info) = self.structs.get(type_path) {
+                if struct_info.module_path == current_module {
+                    output.push_str(&jsdoc(&struct_info.docs, ""));
+                    output.push_str(&self.generate_struct_type(struct_info));
+                    output.push('\n');
+                }
+            } else if let Some(enum_info) = self.enums.get(type_path) {
+                if enum_info.module_path == current_module {
+                    output.push_str(&jsdoc(&enum_info.docs, ""));
+                    output.push_str(&self.generate_enum_type(enum_info));
+                    if self.config.enum_helpers {
+                        output.push('\n');
+                        output.push_str(&self.generate_enum_helpers(enum_info)?);
+                    }
+                    output.push('\n');
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Path of a module's `.types.ts` file, next to its schema file
+    fn types_file(&self, module: &str) -> PathBuf {
+        self.module_file(module)
+            .with_extension(format!("types.{}", typescript_extension(&self.config)))
+    }
+
+    fn import_specifier(&self, module: &str, from_module: &str) -> String {
+        typescript_import_specifier(
+            &self.module_file(module),
+            &self.module_file(from_module),
+            &self.config,
+        )
+    }
+
+    fn generate_struct_type(&self, struct_info: &StructInfo) -> String {
+        let fields: Vec<_> = struct_info
+            .fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "{}    {}: {};\n",
+                    jsdoc(&field.docs, "    "),
+                    self.config.naming.field_key(&field.name),
+                    self.type_to_typescript(&field.type_kind)
+                )
+            })
+            .collect();
+
+        format!(
+            "export interface {} {{\n{}}}\n",
+            self.config.naming.type_name(&struct_info.name),
+            fields.concat()
+        )
+    }
+
+    /// Zorsh decodes enums to single-key objects, so the type is a union of those
+    fn generate_enum_type(&self, enum_info: &EnumInfo) -> String {
+        let variants: Vec<_> = enum_info
+            .variants
+            .iter()
+            .map(|variant| {
+                let payload = self.variant_payload_type(variant);
+                let key = self.config.naming.variant_key(&variant.name);
+                if variant.docs.is_empty() {
+                    format!("\n    | {{ {}: {} }}", key, payload)
+                } else {
+                    // Documented variants are spread over lines so the comment sits on the key
+                    format!(
+                        "\n    | {{\n{}        {}: {};\n    }}",
+                        jsdoc(&variant.docs, "        "),
+                        key,
+                        payload
+                    )
+                }
+            })
+            .collect();
+
+        let type_name = self.config.naming.type_name(&enum_info.name);
+        if variants.is_empty() {
+            return format!("export type {} = never;\n", type_name);
+        }
+        format!("export type {} ={};\n", type_name, variants.concat())
+    }
+
+    /// TypeScript type of a decoded variant's value
+    fn variant_payload_type(&self, variant: &EnumVariant) -> String {
+        match &variant.fields {
+            None => "Record<string, never>".to_string(),
+            Some(fields) if fields.len() == 1 && fields[0].name.is_empty() => {
+                self.type_to_typescript(&fields[0].type_kind)
+            }
+            Some(fields)
+                if is_tuple(fields) && self.config.tuple_variants == TupleVariants::Tuple =>
+            {
+                let types: Vec<_> = fields
+                    .iter()
+                    .map(|field| self.type_to_typescript(&field.type_kind))
+                    .collect();
+                format!("[{}]", types.join(", "))
+            }
+            Some(fields) => {
+                let fields: Vec<_> = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field)| {
+                        format!(
+                            "{}: {}",
+                            self.variant_field_key(&field.name, index),
+                            self.type_to_typescript(&field.type_kind)
+                        )
+                    })
+                    .collect();
+                format!("{{ {} }}", fields.join("; "))
+            }
+        }
+    }
+
+    /// Key of a variant's field; unnamed fields are keyed by position (`_0`, `_1`)
+    fn variant_field_key(&self, name: &str, index: usize) -> String {
+        if name.is_empty() {
+            format!("_{}", index)
+        } else {
+            self.config.naming.field_key(name)
+        }
+    }
+
+    /// Object named after the enum with a constructor and a type guard per variant
+    /// (`Status.Idle()`, `Status.isIdle(value)`) and an exhaustive `match`
+    fn generate_enum_helpers(&self, enum_info: &EnumInfo) -> Result<String> {
+        if enum_info.variants.is_empty() {
+            return Ok(String::new());
+        }
+
+        let type_name = self.config.naming.type_name(&enum_info.name);
+        let mut members = HashSet::from(["match".to_string()]);
+        for variant in &enum_info.variants {
+            for member in [variant.name.clone(), format!("is{}", variant.name)] {
+                if !members.insert(member.clone()) {
+                    bail!(
+                        "The helpers of `{}` would define `{}` twice, rename the variant or disable enum helpers",
+                        enum_info.name,
+                        member
+                    );
+                }
+            }
+        }
+
+        let mut constructors = String::new();
+        let mut guards = String::new();
+        let mut cases = String::new();
+        let mut arms = String::new();
+
+        for variant in &enum_info.variants {
+            let key = self.config.naming.variant_key(&variant.name);
+            let payload = self.variant_payload_type(variant);
+
+            constructors.push_str(&jsdoc(&variant.docs, "    "));
+            if variant.fields.is_none() {
+                constructors.push_str(&format!(
+                    "    {}: (): {} => ({{ {}: {{}} }}),\n",
+                    variant.name, type_name, key
+                ));
+            } else {
+                constructors.push_str(&format!(
+                    "    {}: (value: {}): {} => ({{ {}: value }}),\n",
+                    variant.name, payload, type_name, key
+                ));
+            }
+            guards.push_str(&format!(
+                "    is{}: (value: {}): value is Extract<{}, {{ {}: unknown }}> => '{}' in value,\n",
+                variant.name, type_name, type_name, key, key
+            ));
+            cases.push_str(&format!(
+                "            {}: (value: {}) => R;\n",
+                key, payload
+            ));
+            arms.push_str(&format!(
+                "        if ('{}' in value) return cases.{}(value.{});\n",
+                key, key, key
+            ));
+        }
+
+        let mut output = format!("export const {} = {{\n", type_name);
+        output.push_str(&constructors);
+        output.push_str(&guards);
+        output.push_str(&format!(
+            "    match: <R>(\n        value: {},\n        cases: {{\n{}        }},\n    ): R => {{\n",
+            type_name, cases
+        ));
+        output.push_str(&arms);
+        output.push_str(&format!(
+            "        throw new Error('Unknown {} variant');\n    }},\n}} as const;\n",
+            type_name
+        ));
+        Ok(output)
+    }
+
+    /// Variant keys of a unit-only enum as a constant array, and their Borsh discriminants
+    fn generate_enum_values(&self, enum_info: &EnumInfo) -> String {
+        let keys: Vec<_> = enum_info
+            .variants
+            .iter()
+            .map(|variant| self.config.naming.variant_key(&variant.name))
+            .collect();
+        let values: Vec<_> = keys.iter().map(|key| format!("'{}'", key)).collect();
+        let discriminants: Vec<_> = keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| format!("    {}: {},\n", key, index))
+            .collect();
+
+        format!(
+            "export const {} = [{}] as const;\nexport const {} = {{\n{}}} as const;\n",
+            self.values_name(&enum_info.name),
+            values.join(", "),
+            self.discriminants_name(&enum_info.name),
+            discriminants.concat()
+        )
+    }
+
+    fn size_analyzer(&self) -> SizeAnalyzer<'_> {
+        SizeAnalyzer::new(&self.structs, &self.enums).with_config(&self.config)
+    }
+
+    /// Serialized size of a type, when sizes are exported and every value has the same one
+    fn fixed_size(&self, sizes: &SizeAnalyzer, type_path: &str) -> Option<usize> {
+        if !self.config.size_constants {
+            return None;
+        }
+        match sizes.size_of(type_path) {
+            Ok(Size::Fixed(size)) => Some(size),
+            _ => None,
+        }
+    }
+
+    fn generate_fixed_size(&self, sizes: &SizeAnalyzer, type_path: &str, name: &str) -> String {
+        match self.fixed_size(sizes, type_path) {
+            Some(size) => format!(
+                "\nexport const {} = {};\n",
+                self.fixed_size_name(name),
+                size
+            ),
+            None => String::new(),
+        }
+    }
+
+    fn fixed_size_name(&self, name: &str) -> String {
+        format!("{}FixedSize", self.config.naming.type_name(name))
+    }
+
+    fn values_name(&self, enum_name: &str) -> String {
+        format!("{}Values", self.config.naming.type_name(enum_name))
+    }
+
+    fn discriminants_name(&self, enum_name: &str) -> String {
+        format!("{}Discriminants", self.config.naming.type_name(enum_name))
+    }
+
+    /// TypeScript type of a decoded value
+    fn type_to_typescript(&self, type_kind: &TypeKind) -> String {
+        match type_kind {
+            TypeKind::Primitive(name) => match name.as_str() {
+                "u64" | "u128" | "i64" | "i128" => "bigint".to_string(),
+                "bool" => "boolean".to_string(),
+                _ => "number".to_string(),
+            },
+            TypeKind::String => "string".to_string(),
+            // Overrides are arbitrary Zorsh expressions, whose decoded type isn't known here
+            TypeKind::Struct(name, path) | TypeKind::Enum(name, path) => {
+                if self.type_override(name, path).is_some() {
+                    "unknown".to_string()
+                } else {
+                    self.config.naming.type_name(name)
+                }
+            }
+            TypeKind::Vec(inner) | TypeKind::Array(inner, _) => {
+                let inner = self.type_to_typescript(inner);
+                if inner.contains(' ') {
+                    format!("Array<{}>", inner)
+                } else {
+                    format!("{}[]", inner)
+                }
+            }
+            TypeKind::HashMap(key, value) => format!(
+                "Map<{}, {}>",
+                self.type_to_typescript(key),
+                self.type_to_typescript(value)
+            ),
+            TypeKind::Option(inner) => format!("{} | null", self.type_to_typescript(inner)),
+        }
+    }
+
+    fn type_names(&self, names: &[String]) -> Vec<String> {
+        names
+            .iter()
+            .map(|name| self.config.naming.type_name(name))
+            .collect()
+    }
+
+    fn infer_type(&self, name: &str) -> String {
+        format!(
+            "export type {} = b.infer<typeof {}>;\n",
+            self.config.naming.type_name(name),
+            self.config.naming.schema_name(name)
         )
     }
 
-    #[allow(clippy::only_used_in_recursion)] // Parameter is essential for recursive calls
-    fn type_to_zorsh(&self, type_kind: &TypeKind) -> String {
+    fn type_override(&self, name: &str, path: &str) -> Option<&String> {
+        type_override(&self.config, name, path)
+    }
+
+    /// Schema `{name}Schema` for a type that isn't a struct or enum, with its TypeScript
+    /// type declared the way `type_declarations` asks for
+    pub fn generate_alias(&self, name: &str, type_kind: &TypeKind) -> String {
+        let mut output = format!(
+            "export const {} = {};\n",
+            self.config.naming.schema_name(name),
+            self.type_to_zorsh(type_kind)
+        );
+        match self.config.type_declarations {
+            TypeDeclarations::Infer => output.push_str(&self.infer_type(name)),
+            TypeDeclarations::Interfaces => output.push_str(&format!(
+                "export type {} = {};\n",
+                self.config.naming.type_name(name),
+                self.type_to_typescript(type_kind)
+            )),
+            TypeDeclarations::SeparateFile => {}
+        }
+        output
+    }
+
+    /// Zorsh expression for a type, e.g. `b.vec(ItemSchema)`
+    pub fn type_to_zorsh(&self, type_kind: &TypeKind) -> String {
         match type_kind {
             TypeKind::Primitive(name) => format!("b.{}()", name),
             TypeKind::String => "b.string()".to_string(),
-            TypeKind::Struct(name, _) => format!("{}Schema", name),
-            TypeKind::Enum(name, _) => format!("{}Schema", name),
+            TypeKind::Struct(name, path) | TypeKind::Enum(name, path) => self
+                .type_override(name, path)
+                .cloned()
+                .unwrap_or_else(|| self.config.naming.schema_name(name)),
             TypeKind::Vec(inner) => format!("b.vec({})", self.type_to_zorsh(inner)),
             TypeKind::HashMap(key, value) => format!(
                 "b.hashMap({}, {})",
@@ -152,3 +746,62 @@ impl ZorshGenerator {
         }
     }
 }
+
+/// Looks up a user-supplied Zorsh expression by full type path, then by name
+pub(crate) fn type_override<'a>(config: &'a Config, name: &str, path: &str) -> Option<&'a String> {
+    config
+        .type_overrides
+        .get(path)
+        .or_else(|| config.type_overrides.get(name))
+}
+
+/// Whether the fields are a tuple variant's, which have no names
+pub(crate) fn is_tuple(fields: &[FieldInfo]) -> bool {
+    !fields.is_empty() && fields.iter().all(|field| field.name.is_empty())
+}
+
+/// Whether every variant of the enum is a unit variant, like a C enum
+fn is_unit_only(enum_info: &EnumInfo) -> bool {
+    !enum_info.variants.is_empty()
+        && enum_info
+            .variants
+            .iter()
+            .all(|variant| variant.fields.is_none())
+}
+
+/// JSDoc block for an item's docs, indented to match it, or nothing if it has none
+fn jsdoc(docs: &Docs, indent: &str) -> String {
+    // A `*/` in the text would end the comment early
+    let mut lines: Vec<_> = docs
+        .lines
+        .iter()
+        .map(|line| line.replace("*/", "*\\/"))
+        .collect();
+    if let Some(note) = &docs.deprecated {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(
+            format!("@deprecated {}", note.replace("*/", "*\\/"))
+                .trim_end()
+                .to_string(),
+        );
+    }
+
+    match lines.as_slice() {
+        [] => String::new(),
+        [line] => format!("{}/** {} */\n", indent, line),
+        lines => {
+            let mut output = format!("{}/**\n", indent);
+            for line in lines {
+                if line.is_empty() {
+                    output.push_str(&format!("{} *\n", indent));
+                } else {
+                    output.push_str(&format!("{} * {}\n", indent, line));
+                }
+            }
+            output.push_str(&format!("{} */\n", indent));
+            output
+        }
+    }
+}
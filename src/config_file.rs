@@ -0,0 +1,216 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::Config;
+
+/// Name of the standalone project configuration file
+pub const CONFIG_FILE_NAME: &str = "zorsh.toml";
+
+/// Starter configuration written by `zorsh-gen-rs init`
+pub const CONFIG_TEMPLATE: &str = r#"# zorsh-gen-rs configuration
+# Command line flags take precedence over the values in this file.
+
+# Directory containing the Rust sources to convert (relative to this file)
+input = "src"
+
+# Directory the generated TypeScript files are written to (relative to this file)
+output = "generated"
+
+# Only process types with #[derive(BorshSerialize)] or #[derive(BorshDeserialize)]
+only_annotated = true
+
+# Skip files and directories matching these gitignore-style globs (relative to `input`)
+ignored_patterns = ["tests/", "examples/", "target/"]
+
+# Only process files matching these globs, e.g. ["models/**"] (everything when empty)
+include_patterns = []
+
+# Also skip files excluded by .gitignore
+respect_gitignore = false
+
+# Output directory structure: "nested" or "flat"
+output_structure = "nested"
+
+# Code generation backend: "zorsh", "borsh-js", "python", "json-schema" or "borsh-schema"
+backend = "zorsh"
+
+# Write every module into a single file (json-schema backend)
+bundle = false
+
+# TypeScript types next to the schemas: "infer" (`b.infer<typeof FooSchema>`),
+# "interfaces" (explicit interfaces and unions) or "separate-file" (in `.types.ts` files)
+type_declarations = "infer"
+
+# Extension of relative imports: "none" (`./player`), "js" (`./player.js`, for
+# NodeNext module resolution) or "ts" (`./player.ts`)
+import_extension = "none"
+
+# Module system of the generated TypeScript: "esm" (.ts files) or "commonjs"
+# (.cts files, whose imports use .cjs/.cts extensions)
+module_format = "esm"
+
+# Write an index.ts re-exporting every module in each output directory
+barrels = false
+
+# Emit an object per enum with variant constructors (`Status.Idle()`), type guards
+# (`Status.isIdle(value)`) and an exhaustive `Status.match(value, cases)`
+enum_helpers = false
+
+# Export the variant names and discriminants of enums whose variants carry no data,
+# like `StatusValues = ['Active', 'Inactive']` and `StatusDiscriminants = { Active: 0, ... }`
+enum_values = false
+
+# Encoding of enum variants with several unnamed fields, like `Move(i32, i32)`:
+# "tuple" (`b.tuple(...)`) or "struct" (`b.struct({ _0: ..., _1: ... })`)
+tuple_variants = "tuple"
+
+# Write a `*.test.ts` next to each module that round-trips sample values of its types
+# through their schemas: "none", "vitest" or "jest"
+test_suites = "none"
+
+# Export the serialized size of each fixed-size type, like `export const PointFixedSize = 8;`
+size_constants = false
+
+# Remove every previously generated file before writing new ones
+# (files of deleted Rust modules are always removed)
+clean_output = false
+
+# Names of the generated schemas, types and keys (zorsh backend). In templates,
+# `{Name}` is the Rust type name and `{name}` the same in camelCase.
+# Cases: "preserve", "camel", "snake" or "pascal"
+[naming]
+schema_name = "{Name}Schema"
+type_name = "{Name}"
+field_case = "preserve"
+variant_case = "preserve"
+
+# Header at the top of generated files marking them as not to be edited by hand,
+# with the generator version, the source files and a hash of the contents
+# (TypeScript and Python output)
+[header]
+enabled = false
+version = true
+sources = true
+hash = false
+
+# Zorsh expressions used in place of Rust types, keyed by type name or full path
+[type_overrides]
+# U128 = "b.u128()"
+
+# BorshSchemaContainer JSON files (relative to this file) whose types are generated
+# alongside the Rust sources, keyed by the module they are placed in
+[borsh_schemas]
+# "external::tokens" = "schemas/tokens.json"
+"#;
+
+/// Settings loaded from `zorsh.toml` or the `[package.metadata.zorsh]` table of `Cargo.toml`
+#[derive(Debug, Clone, Default)]
+pub struct ConfigFile {
+    /// File the settings were read from
+    pub path: PathBuf,
+    /// Input directory, resolved relative to the configuration file
+    pub input: Option<PathBuf>,
+    /// Output directory, resolved relative to the configuration file
+    pub output: Option<PathBuf>,
+    /// Generator settings, with defaults for anything the file leaves out
+    pub config: Config,
+}
+
+impl ConfigFile {
+    /// Looks for a configuration file in `start_dir` and each of its ancestors.
+    ///
+    /// In every directory `zorsh.toml` wins over `Cargo.toml`; a `Cargo.toml`
+    /// without a `[package.metadata.zorsh]` table is skipped.
+    pub fn discover<P: AsRef<Path>>(start_dir: P) -> Result<Option<Self>> {
+        for dir in start_dir.as_ref().ancestors() {
+            let config_path = dir.join(CONFIG_FILE_NAME);
+            if config_path.is_file() {
+                return Self::load(&config_path).map(Some);
+            }
+
+            let manifest_path = dir.join("Cargo.toml");
+            if manifest_path.is_file() {
+                let content = read(&manifest_path)?;
+                if let Some(table) = cargo_metadata_table(&content, &manifest_path)? {
+                    return Self::from_table(table, &manifest_path).map(Some);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Loads a configuration file, reading `Cargo.toml` manifests from their metadata table
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = read(path)?;
+
+        if path.file_name().is_some_and(|name| name == "Cargo.toml") {
+            let table = cargo_metadata_table(&content, path)?.ok_or_else(|| {
+                anyhow!("No [package.metadata.zorsh] table in {}", path.display())
+            })?;
+            return Self::from_table(table, path);
+        }
+
+        let table: toml::Table = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Self::from_table(table, path)
+    }
+
+    fn from_table(mut table: toml::Table, path: &Path) -> Result<Self> {
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let mut take_path = |key: &str| -> Result<Option<PathBuf>> {
+            match table.remove(key) {
+                None => Ok(None),
+                Some(toml::Value::String(value)) => Ok(Some(base_dir.join(value))),
+                Some(_) => Err(anyhow!("`{}` in {} must be a string", key, path.display())),
+            }
+        };
+
+        let input = take_path("input")?;
+        let output = take_path("output")?;
+        let mut config: Config = table
+            .try_into()
+            .with_context(|| format!("Invalid configuration in {}", path.display()))?;
+        for schema_path in config.borsh_schemas.values_mut() {
+            *schema_path = base_dir.join(&schema_path);
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            input,
+            output,
+            config,
+        })
+    }
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read file: {}", path.display()))
+}
+
+fn cargo_metadata_table(content: &str, path: &Path) -> Result<Option<toml::Table>> {
+    let mut manifest: toml::Table =
+        toml::from_str(content).with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let table = manifest
+        .remove("package")
+        .and_then(|package| match package {
+            toml::Value::Table(mut package) => package.remove("metadata"),
+            _ => None,
+        })
+        .and_then(|metadata| match metadata {
+            toml::Value::Table(mut metadata) => metadata.remove("zorsh"),
+            _ => None,
+        });
+
+    match table {
+        None => Ok(None),
+        Some(toml::Value::Table(table)) => Ok(Some(table)),
+        Some(_) => Err(anyhow!(
+            "[package.metadata.zorsh] in {} must be a table",
+            path.display()
+        )),
+    }
+}
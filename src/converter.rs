@@ -1,76 +1,103 @@
 use anyhow::{Context, Result};
-use std::collections::{HashMap, HashSet};
+use log::warn;
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::code_generator::ZorshGenerator;
-use crate::dependency_resolver::DependencyResolver;
+use crate::generation::{self, Diagnostic};
+use crate::header;
+use crate::ir::TypeIr;
+use crate::manifest::Manifest;
 use crate::source_loader::SourceLoader;
-use crate::type_parser::TypeParser;
-use crate::OutputStructure;
+
+/// Where the converter reads its types from
+enum ConverterInput {
+    /// A directory (or single file) of Rust sources
+    Rust(SourceLoader),
+    /// A JSON file previously written with `with_ir_output`
+    Ir(PathBuf),
+}
 
 pub struct ZorshConverter {
-    source_loader: SourceLoader,
+    input: ConverterInput,
     output_dir: PathBuf,
+    ir_output: Option<PathBuf>,
     config: crate::Config,
 }
 
 impl ZorshConverter {
     pub fn new<P: AsRef<Path>>(input_path: P, output_path: P, config: crate::Config) -> Self {
         Self {
-            source_loader: SourceLoader::new(input_path, config.ignored_patterns.clone()),
+            input: ConverterInput::Rust(
+                SourceLoader::new(input_path, config.ignored_patterns.clone())
+                    .with_include_patterns(config.include_patterns.clone())
+                    .with_gitignore(config.respect_gitignore),
+            ),
             output_dir: output_path.as_ref().to_path_buf(),
+            ir_output: None,
             config,
         }
     }
 
-    fn get_output_path(&self, module_path: &str) -> PathBuf {
-        match self.config.output_structure {
-            OutputStructure::Nested => self
-                .output_dir
-                .join(format!("{}.ts", module_path.replace("::", "/")).to_lowercase()),
-            OutputStructure::Flat => self
-                .output_dir
-                .join(format!("{}.ts", module_path.replace("::", "_")).to_lowercase()),
+    /// Generate from a dumped IR file instead of parsing Rust sources
+    pub fn from_ir<P: AsRef<Path>>(ir_path: P, output_path: P, config: crate::Config) -> Self {
+        Self {
+            input: ConverterInput::Ir(ir_path.as_ref().to_path_buf()),
+            output_dir: output_path.as_ref().to_path_buf(),
+            ir_output: None,
+            config,
         }
     }
 
-    pub fn convert(&self) -> Result<()> {
-        // Find and load all Rust files
-        let source_files = self.source_loader.discover_rust_files()?;
+    /// Also write the parsed and resolved types to this JSON file
+    pub fn with_ir_output<P: AsRef<Path>>(mut self, ir_path: P) -> Self {
+        self.ir_output = Some(ir_path.as_ref().to_path_buf());
+        self
+    }
 
-        // Parse types from each file
-        let mut all_structs = HashMap::new();
-        let mut all_enums = HashMap::new();
+    fn build_ir(&self) -> Result<(TypeIr, Vec<Diagnostic>)> {
+        match &self.input {
+            ConverterInput::Rust(source_loader) => {
+                // Find and load all Rust files
+                let source_files = source_loader.discover_rust_files()?;
+                let (mut ir, diagnostics) = generation::build_ir(&source_files, &self.config)?;
 
-        for source_file in &source_files {
-            let mut parser =
-                TypeParser::new(source_file.module_path.clone(), self.config.only_annotated);
-            parser.parse_file(&source_file.content)?;
+                // Sources are recorded relative to the input, so headers and IR files
+                // don't depend on where the project is checked out
+                let root_dir = source_loader.root_dir();
+                for paths in ir.sources.values_mut() {
+                    *paths = paths
+                        .iter()
+                        .map(|path| relative_to(root_dir, path))
+                        .collect();
+                }
+                Ok((ir, diagnostics))
+            }
+            ConverterInput::Ir(ir_path) => Ok((TypeIr::load(ir_path)?, Vec::new())),
+        }
+    }
 
-            all_structs.extend(parser.structs);
-            all_enums.extend(parser.enums);
+    pub fn convert(&self) -> Result<()> {
+        let (ir, mut diagnostics) = self.build_ir()?;
+
+        if let Some(ir_output) = &self.ir_output {
+            ir.save(ir_output)?;
         }
 
-        // Resolve dependencies
-        let resolver = DependencyResolver::new(all_structs.clone(), all_enums.clone());
-        let dependencies = resolver.resolve()?;
-
-        // Get unique set of modules
-        let mut modules = HashSet::new();
-        for type_path in &dependencies.ordered_types {
-            if let Some(struct_info) = all_structs.get(type_path) {
-                modules.insert(struct_info.module_path.clone());
-            } else if let Some(enum_info) = all_enums.get(type_path) {
-                modules.insert(enum_info.module_path.clone());
-            }
+        let generated = generation::generate_ir(&ir, &self.config)?;
+        diagnostics.extend(generated.diagnostics);
+        for diagnostic in &diagnostics {
+            warn!("{}", diagnostic);
         }
 
-        // Generate code for each module
-        let generator = ZorshGenerator::new(all_structs, all_enums);
+        // Files from the previous run are only ever removed if we generated them
+        let previous = Manifest::load(&self.output_dir)?;
+        if self.config.clean_output {
+            previous.remove_files(&self.output_dir, &BTreeSet::new())?;
+        }
 
-        for module in modules {
-            let file_path = self.get_output_path(&module);
+        for (relative_path, code) in &generated.files {
+            let file_path = self.output_dir.join(relative_path);
 
             // Create parent directories if they don't exist
             if let Some(parent) = file_path.parent() {
@@ -78,12 +105,76 @@ impl ZorshConverter {
                     .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
             }
 
-            // Generate and write code
-            let code = generator.generate_module(&module, &dependencies)?;
             fs::write(&file_path, code)
                 .with_context(|| format!("Failed to write file: {}", file_path.display()))?;
         }
 
+        // Remove files left behind by modules that no longer exist
+        let written: BTreeSet<_> = generated.files.into_keys().collect();
+        previous.remove_files(&self.output_dir, &written)?;
+
+        fs::create_dir_all(&self.output_dir).with_context(|| {
+            format!("Failed to create directory: {}", self.output_dir.display())
+        })?;
+        Manifest::new(written).save(&self.output_dir)?;
+
         Ok(())
     }
+
+    /// Compares the output directory with what `convert` would write, without changing
+    /// anything, returning one diagnostic per file that is missing, out of date, edited
+    /// by hand or no longer generated
+    pub fn check(&self) -> Result<Vec<Diagnostic>> {
+        let (ir, _) = self.build_ir()?;
+        let generated = generation::generate_ir(&ir, &self.config)?;
+        let mut problems = Vec::new();
+
+        for (relative_path, code) in &generated.files {
+            let file_path = self.output_dir.join(relative_path);
+            let message = match fs::read_to_string(&file_path) {
+                Err(_) => "missing",
+                Ok(existing) if existing == *code => continue,
+                Ok(existing) if header::is_hand_edited(relative_path, &existing) => {
+                    "edited by hand"
+                }
+                Ok(_) => "out of date",
+            };
+            problems.push(Diagnostic {
+                location: relative_path.display().to_string(),
+                message: message.to_string(),
+            });
+        }
+
+        let previous = Manifest::load(&self.output_dir)?;
+        for relative_path in &previous.files {
+            if !generated.files.contains_key(relative_path)
+                && self.output_dir.join(relative_path).is_file()
+            {
+                problems.push(Diagnostic {
+                    location: relative_path.display().to_string(),
+                    message: "no longer generated".to_string(),
+                });
+            }
+        }
+
+        Ok(problems)
+    }
+}
+
+/// `path` relative to the directory `base`, going up with `..` where needed. Both are
+/// canonicalized first, as one may be absolute and the other relative.
+fn relative_to(base: &Path, path: &Path) -> PathBuf {
+    let (Ok(base), Ok(path)) = (base.canonicalize(), path.canonicalize()) else {
+        return path.to_path_buf();
+    };
+    let base: Vec<_> = base.components().collect();
+    let path: Vec<_> = path.components().collect();
+    let common = base.iter().zip(&path).take_while(|(a, b)| a == b).count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    relative.extend(&path[common..]);
+    relative
 }
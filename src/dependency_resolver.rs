@@ -3,15 +3,16 @@ use anyhow::{anyhow, Result};
 use log::debug;
 use petgraph::algo::toposort;
 use petgraph::prelude::*;
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 /// Represents all type dependencies across modules
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeDependencies {
     /// List of all types in dependency order
     pub ordered_types: Vec<String>,
     /// Map of module path -> set of types that need to be imported from it
-    pub module_imports: HashMap<String, HashSet<String>>,
+    pub module_imports: BTreeMap<String, BTreeSet<String>>,
 }
 
 pub struct DependencyResolver {
@@ -27,10 +28,12 @@ impl DependencyResolver {
     pub fn resolve(&self) -> Result<TypeDependencies> {
         // Build dependency graph
         let mut graph = Graph::<String, ()>::new();
-        let mut node_indices = HashMap::new();
+        let mut node_indices = BTreeMap::new();
 
-        // Create nodes for all types
-        for path in self.structs.keys().chain(self.enums.keys()) {
+        // Create nodes for all types, sorted so the output order is stable
+        let mut paths: Vec<_> = self.structs.keys().chain(self.enums.keys()).collect();
+        paths.sort();
+        for path in paths {
             let idx = graph.add_node(path.clone());
             node_indices.insert(path.clone(), idx);
         }
@@ -58,7 +61,7 @@ impl DependencyResolver {
             .collect::<Vec<_>>();
 
         // Collect required imports between modules
-        let mut module_imports: HashMap<String, HashSet<String>> = HashMap::new();
+        let mut module_imports: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
 
         for type_path in &ordered_types {
             let current_module = self.get_module_path(type_path);
@@ -90,8 +93,8 @@ impl DependencyResolver {
         })
     }
 
-    fn get_type_dependencies(&self, type_path: &str) -> Option<HashSet<String>> {
-        let mut deps = HashSet::new();
+    fn get_type_dependencies(&self, type_path: &str) -> Option<BTreeSet<String>> {
+        let mut deps = BTreeSet::new();
 
         debug!("Getting dependencies for type: {}", type_path);
 
@@ -121,7 +124,7 @@ impl DependencyResolver {
     }
 
     #[allow(clippy::only_used_in_recursion)] // Parameter is essential for recursive calls
-    fn collect_type_dependencies(&self, type_kind: &TypeKind, deps: &mut HashSet<String>) {
+    fn collect_type_dependencies(&self, type_kind: &TypeKind, deps: &mut BTreeSet<String>) {
         match type_kind {
             TypeKind::Struct(name, path) | TypeKind::Enum(name, path) => {
                 debug!("  Adding dependency: {} ({})", name, path);
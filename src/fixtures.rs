@@ -0,0 +1,361 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::backend::{typescript_extension, typescript_import_specifier, typescript_module_file};
+use crate::borsh_codec::{to_hex, BorshCodec};
+use crate::code_generator::{is_tuple, type_override};
+use crate::ir::TypeIr;
+use crate::test_suites::{framework_import, object, unknown_encoding, variant_literal};
+use crate::type_parser::{EnumInfo, EnumVariant, FieldInfo, StructInfo, TypeKind};
+use crate::Config;
+
+/// A JSON sample value and its Borsh encoding
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    /// Path of the JSON file, relative to the fixtures directory
+    pub file: PathBuf,
+    /// Full path of the Rust type of the value
+    pub type_path: String,
+    /// The value as the codec decodes it back, so every value of a type has the same shape
+    pub value: Value,
+    pub bytes: Vec<u8>,
+}
+
+/// Turns a directory of JSON samples into Borsh test vectors and a TypeScript test
+/// checking the generated schemas decode them to the same values.
+///
+/// Each JSON file is named by the path of its type, with `::` or directories between
+/// the segments (`models::Player.json`, `models/Player.json`), optionally followed by a
+/// label to keep several samples of a type apart (`models/Player/empty.json`).
+pub struct FixtureGenerator {
+    codec: BorshCodec,
+    structs: HashMap<String, StructInfo>,
+    enums: HashMap<String, EnumInfo>,
+    config: Config,
+}
+
+impl FixtureGenerator {
+    pub fn new(ir: &TypeIr, config: Config) -> Self {
+        Self {
+            codec: BorshCodec::from_ir(ir),
+            structs: ir.struct_map(),
+            enums: ir.enum_map(),
+            config,
+        }
+    }
+
+    /// Encodes every `*.json` file under `dir`, in path order
+    pub fn load_dir<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<Fixture>> {
+        let dir = dir.as_ref();
+        let mut files = Vec::new();
+        collect_json_files(dir, &mut files)?;
+        files.sort();
+
+        files
+            .into_iter()
+            .map(|path| {
+                let json = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read file: {}", path.display()))?;
+                let value = serde_json::from_str(&json)
+                    .with_context(|| format!("Failed to parse JSON: {}", path.display()))?;
+                let file = path.strip_prefix(dir).unwrap_or(&path);
+                self.fixture(file, &value)
+                    .with_context(|| format!("Invalid fixture: {}", path.display()))
+            })
+            .collect()
+    }
+
+    /// Encodes the value of a JSON file at `file`, relative to the fixtures directory
+    pub fn fixture(&self, file: &Path, value: &Value) -> Result<Fixture> {
+        let type_path = self.fixture_type(file)?;
+        let bytes = self.codec.encode(&type_path, value)?;
+        let value = self.codec.decode(&type_path, &bytes)?;
+
+        Ok(Fixture {
+            file: file.to_path_buf(),
+            type_path,
+            value,
+            bytes,
+        })
+    }
+
+    /// Type named by a fixture's path, with or without a trailing label
+    fn fixture_type(&self, file: &Path) -> Result<String> {
+        let segments: Vec<String> = file
+            .with_extension("")
+            .components()
+            .flat_map(|component| {
+                component
+                    .as_os_str()
+                    .to_string_lossy()
+                    .split("::")
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let with_label = segments[..segments.len().saturating_sub(1)].join("::");
+        self.codec
+            .resolve_type(&segments.join("::"))
+            .or_else(|error| {
+                if with_label.is_empty() {
+                    Err(error)
+                } else {
+                    self.codec.resolve_type(&with_label).map_err(|_| error)
+                }
+            })
+    }
+
+    /// TypeScript test decoding each fixture with its schema, to be written at `test_file`
+    /// in the output directory
+    pub fn test_suite(&self, test_file: &Path, fixtures: &[Fixture]) -> String {
+        let mut imports: BTreeMap<PathBuf, BTreeSet<String>> = BTreeMap::new();
+        let mut suites: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+
+        for fixture in fixtures {
+            let label = fixture
+                .file
+                .with_extension("")
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let tests = suites.entry(&fixture.type_path).or_default();
+            let type_kind = TypeKind::named(&fixture.type_path, &self.structs);
+
+            match self.literal(&type_kind, &fixture.value) {
+                Ok(expected) => {
+                    let name = type_kind_name(&type_kind);
+                    let schema_name = self.config.naming.schema_name(name);
+                    let type_name = self.config.naming.type_name(name);
+                    tests.push(test(
+                        &label,
+                        &type_name,
+                        &schema_name,
+                        &expected,
+                        &to_hex(&fixture.bytes),
+                    ));
+
+                    let module = fixture.type_path.rsplit_once("::").unwrap_or_default().0;
+                    let names = imports
+                        .entry(typescript_module_file(module, &self.config))
+                        .or_default();
+                    names.insert(schema_name);
+                    names.insert(format!("type {}", type_name));
+                }
+                Err(unknown) => {
+                    tests.push(format!("    // {}\n", unknown_encoding(&label, &unknown)))
+                }
+            }
+        }
+
+        let mut output = framework_import(&self.config);
+        for (module_file, names) in &imports {
+            output.push_str(&format!(
+                "import {{ {} }} from '{}';\n",
+                names.iter().cloned().collect::<Vec<_>>().join(", "),
+                typescript_import_specifier(test_file, module_file, &self.config)
+            ));
+        }
+        output.push_str(
+            "\nconst bytes = (hex: string) =>\n    \
+             Uint8Array.from(hex.match(/../g) ?? [], (byte) => parseInt(byte, 16));\n",
+        );
+
+        for (type_path, tests) in suites {
+            output.push_str(&format!("\ndescribe({}, () => {{\n", quote(type_path)));
+            output.push_str(&tests.join("\n"));
+            output.push_str("});\n");
+        }
+
+        output
+    }
+
+    /// Path of the test file in the output directory
+    pub fn test_file(&self) -> PathBuf {
+        PathBuf::from(format!(
+            "fixtures.test.{}",
+            typescript_extension(&self.config)
+        ))
+    }
+
+    /// TypeScript literal of a decoded value, or the name of a type whose Zorsh
+    /// encoding isn't known
+    fn literal(&self, type_kind: &TypeKind, value: &Value) -> Result<String, String> {
+        match type_kind {
+            TypeKind::Primitive(name) => Ok(match (name.as_str(), value) {
+                ("u64" | "i64" | "u128" | "i128", Value::String(number)) => format!("{}n", number),
+                _ => value.to_string(),
+            }),
+            TypeKind::String => Ok(quote(value.as_str().unwrap_or_default())),
+            TypeKind::Struct(name, path) | TypeKind::Enum(name, path) => {
+                if type_override(&self.config, name, path).is_some() {
+                    return Err(name.clone());
+                }
+
+                if let Some(struct_info) = self.structs.get(path) {
+                    self.fields_literal(&struct_info.fields, value)
+                } else if let Some(enum_info) = self.enums.get(path) {
+                    let (variant_name, payload) = value
+                        .as_object()
+                        .and_then(|object| object.iter().next())
+                        .ok_or_else(|| name.clone())?;
+                    let variant = enum_info
+                        .variants
+                        .iter()
+                        .find(|variant| variant.name == *variant_name)
+                        .ok_or_else(|| name.clone())?;
+                    self.variant_literal(variant, payload)
+                } else {
+                    Err(name.clone())
+                }
+            }
+            TypeKind::Vec(inner) | TypeKind::Array(inner, _) => {
+                let items = value
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|item| self.literal(inner, item))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(format!("[{}]", items.join(", ")))
+            }
+            TypeKind::Option(inner) => match value {
+                Value::Null => Ok("null".to_string()),
+                value => self.literal(inner, value),
+            },
+            TypeKind::HashMap(key_kind, value_kind) => {
+                let entries: Vec<(Value, &Value)> = match value {
+                    Value::Object(object) => object
+                        .iter()
+                        .map(|(key, value)| (Value::String(key.clone()), value))
+                        .collect(),
+                    Value::Array(pairs) => pairs
+                        .iter()
+                        .filter_map(|pair| Some((pair.get(0)?.clone(), pair.get(1)?)))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                if entries.is_empty() {
+                    return Ok("new Map()".to_string());
+                }
+
+                let entries = entries
+                    .iter()
+                    .map(|(key, value)| {
+                        Ok(format!(
+                            "[{}, {}]",
+                            self.literal(key_kind, key)?,
+                            self.literal(value_kind, value)?
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                Ok(format!("new Map([{}])", entries.join(", ")))
+            }
+        }
+    }
+
+    /// A decoded variant: a single-key object holding the variant's value
+    fn variant_literal(&self, variant: &EnumVariant, payload: &Value) -> Result<String, String> {
+        let values = match &variant.fields {
+            None => Vec::new(),
+            Some(fields) if is_tuple(fields) && fields.len() == 1 => {
+                vec![self.literal(&fields[0].type_kind, payload)?]
+            }
+            Some(fields) if is_tuple(fields) => fields
+                .iter()
+                .zip(payload.as_array().into_iter().flatten())
+                .map(|(field, item)| self.literal(&field.type_kind, item))
+                .collect::<Result<_, _>>()?,
+            Some(fields) => fields
+                .iter()
+                .map(|field| self.literal(&field.type_kind, &payload[&field.name]))
+                .collect::<Result<_, _>>()?,
+        };
+        Ok(variant_literal(variant, &values, &self.config))
+    }
+
+    /// Object literal of the named fields of a decoded struct or variant
+    fn fields_literal(&self, fields: &[FieldInfo], value: &Value) -> Result<String, String> {
+        let entries = fields
+            .iter()
+            .map(|field| {
+                let literal = self.literal(&field.type_kind, &value[&field.name])?;
+                Ok(format!(
+                    "{}: {}",
+                    self.config.naming.field_key(&field.name),
+                    literal
+                ))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(object(&entries))
+    }
+}
+
+/// Writes the `.bin` and `.hex` encodings next to each fixture's JSON file
+pub fn write_vectors<P: AsRef<Path>>(dir: P, fixtures: &[Fixture]) -> Result<()> {
+    for fixture in fixtures {
+        let path = dir.as_ref().join(&fixture.file);
+        for (extension, contents) in [
+            ("bin", fixture.bytes.clone()),
+            ("hex", format!("{}\n", to_hex(&fixture.bytes)).into_bytes()),
+        ] {
+            let path = path.with_extension(extension);
+            fs::write(&path, contents)
+                .with_context(|| format!("Failed to write file: {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn collect_json_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_json_files(&path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn type_kind_name(type_kind: &TypeKind) -> &str {
+    match type_kind {
+        TypeKind::Struct(name, _) | TypeKind::Enum(name, _) => name,
+        _ => "",
+    }
+}
+
+fn test(label: &str, type_name: &str, schema_name: &str, expected: &str, hex: &str) -> String {
+    let mut output = format!("    it({}, () => {{\n", quote(label));
+    output.push_str(&format!(
+        "        const expected: {} = {};\n",
+        type_name, expected
+    ));
+    output.push_str(&format!("        const encoded = bytes('{}');\n", hex));
+    output.push_str(&format!(
+        "        expect({}.deserialize(encoded)).toEqual(expected);\n",
+        schema_name
+    ));
+    output.push_str(&format!(
+        "        expect({}.serialize(expected)).toEqual(encoded);\n",
+        schema_name
+    ));
+    output.push_str("    });\n");
+    output
+}
+
+/// Single-quoted string literal
+fn quote(text: &str) -> String {
+    let escaped = serde_json::to_string(text).unwrap_or_default();
+    let inner = &escaped[1..escaped.len() - 1];
+    format!("'{}'", inner.replace("\\\"", "\"").replace('\'', "\\'"))
+}
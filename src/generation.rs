@@ -0,0 +1,232 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::backend::Backend;
+use crate::borsh_schema_loader::BorshSchemaLoader;
+use crate::ir::TypeIr;
+use crate::source_loader::SourceFile;
+use crate::type_parser::{EnumInfo, StructInfo, TypeKind, TypeParser};
+use crate::Config;
+
+/// A problem found while generating that doesn't stop generation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Type (and field) the problem was found in, e.g. `models::Player.inventory`
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+/// Result of an in-memory generation run
+#[derive(Debug, Default)]
+pub struct GeneratedFiles {
+    /// Generated file contents, keyed by path relative to the output directory
+    pub files: BTreeMap<PathBuf, String>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Parses, resolves and generates code for `sources` without writing to the file system
+pub fn generate(sources: &[SourceFile], config: &Config) -> Result<GeneratedFiles> {
+    let (ir, mut diagnostics) = build_ir(sources, config)?;
+
+    let mut generated = generate_ir(&ir, config)?;
+    diagnostics.append(&mut generated.diagnostics);
+    generated.diagnostics = diagnostics;
+
+    Ok(generated)
+}
+
+/// Parses `sources`, loads the configured borsh schema files and resolves the
+/// dependencies between their types
+pub fn build_ir(sources: &[SourceFile], config: &Config) -> Result<(TypeIr, Vec<Diagnostic>)> {
+    let mut all_structs = HashMap::new();
+    let mut all_enums = HashMap::new();
+    let mut module_sources: BTreeMap<String, BTreeSet<PathBuf>> = BTreeMap::new();
+    let mut diagnostics = Vec::new();
+
+    for source_file in sources {
+        let mut parser = TypeParser::new(source_file.module_path.clone(), config.only_annotated);
+        parser.parse_file(&source_file.content)?;
+
+        for path in parser.structs.keys().chain(parser.enums.keys()) {
+            if all_structs.contains_key(path) || all_enums.contains_key(path) {
+                diagnostics.push(Diagnostic {
+                    location: path.clone(),
+                    message: format!(
+                        "defined more than once, using the definition in {}",
+                        source_file.path.display()
+                    ),
+                });
+            }
+        }
+
+        if !parser.structs.is_empty() || !parser.enums.is_empty() {
+            module_sources
+                .entry(source_file.module_path.clone())
+                .or_default()
+                .insert(source_file.path.clone());
+        }
+        all_structs.extend(parser.structs);
+        all_enums.extend(parser.enums);
+    }
+
+    for (module_path, schema_path) in &config.borsh_schemas {
+        let mut loader = BorshSchemaLoader::new(module_path.clone());
+        loader.load_file(schema_path)?;
+
+        for path in loader.structs.keys().chain(loader.enums.keys()) {
+            if all_structs.contains_key(path) || all_enums.contains_key(path) {
+                diagnostics.push(Diagnostic {
+                    location: path.clone(),
+                    message: format!(
+                        "defined more than once, using the definition in {}",
+                        schema_path.display()
+                    ),
+                });
+            }
+        }
+
+        module_sources
+            .entry(module_path.clone())
+            .or_default()
+            .insert(schema_path.clone());
+        all_structs.extend(loader.structs);
+        all_enums.extend(loader.enums);
+    }
+
+    let ir = TypeIr::new(all_structs, all_enums)?.with_sources(module_sources);
+    Ok((ir, diagnostics))
+}
+
+/// Generates code for already parsed and resolved types with the configured backend
+pub fn generate_ir(ir: &TypeIr, config: &Config) -> Result<GeneratedFiles> {
+    let backend = config
+        .backend
+        .create(ir.struct_map(), ir.enum_map(), config);
+    generate_ir_with(ir, config, backend.as_ref())
+}
+
+/// Generates code for already parsed and resolved types with a custom backend
+pub fn generate_ir_with(
+    ir: &TypeIr,
+    config: &Config,
+    backend: &dyn Backend,
+) -> Result<GeneratedFiles> {
+    let structs = ir.struct_map();
+    let enums = ir.enum_map();
+    let dependencies = &ir.dependencies;
+    let diagnostics = find_unresolved_types(&structs, &enums, config);
+
+    // Get unique set of modules
+    let mut modules = BTreeSet::new();
+    for type_path in &dependencies.ordered_types {
+        if let Some(struct_info) = structs.get(type_path) {
+            modules.insert(struct_info.module_path.clone());
+        } else if let Some(enum_info) = enums.get(type_path) {
+            modules.insert(enum_info.module_path.clone());
+        }
+    }
+
+    let mut files = backend.generate_files(&modules, dependencies)?;
+
+    if config.header.enabled {
+        // Files are matched to their module by path without extensions, which also
+        // covers companions like `player.types.ts`
+        let file_modules: HashMap<_, _> = modules
+            .iter()
+            .map(|module| (extensionless(&backend.module_file(module)), module))
+            .collect();
+        let no_sources = BTreeSet::new();
+
+        for (path, content) in files.iter_mut() {
+            let sources = file_modules
+                .get(&extensionless(path))
+                .and_then(|module| ir.sources.get(*module))
+                .unwrap_or(&no_sources);
+            *content = config.header.apply(path, content, sources);
+        }
+    }
+
+    Ok(GeneratedFiles { files, diagnostics })
+}
+
+/// `models/player.types.ts` becomes `models/player`
+fn extensionless(path: &Path) -> PathBuf {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let stem = file_name.split('.').next().unwrap_or_default();
+    path.with_file_name(stem)
+}
+
+/// Reports references to types that were neither parsed nor overridden
+fn find_unresolved_types(
+    structs: &HashMap<String, StructInfo>,
+    enums: &HashMap<String, EnumInfo>,
+    config: &Config,
+) -> Vec<Diagnostic> {
+    let mut fields = Vec::new();
+    for (path, struct_info) in structs {
+        for field in &struct_info.fields {
+            fields.push((format!("{}.{}", path, field.name), &field.type_kind));
+        }
+    }
+    for (path, enum_info) in enums {
+        for variant in &enum_info.variants {
+            for (index, field) in variant.fields.iter().flatten().enumerate() {
+                let field_name = if field.name.is_empty() {
+                    index.to_string()
+                } else {
+                    field.name.clone()
+                };
+                fields.push((
+                    format!("{}::{}.{}", path, variant.name, field_name),
+                    &field.type_kind,
+                ));
+            }
+        }
+    }
+    fields.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut diagnostics = Vec::new();
+    for (location, type_kind) in fields {
+        let mut references = Vec::new();
+        collect_references(type_kind, &mut references);
+
+        for (name, path) in references {
+            let known = structs.contains_key(path)
+                || enums.contains_key(path)
+                || config.type_overrides.contains_key(path)
+                || config.type_overrides.contains_key(name);
+            if !known {
+                diagnostics.push(Diagnostic {
+                    location: location.clone(),
+                    message: format!("unresolved type `{}`", path),
+                });
+            }
+        }
+    }
+
+    diagnostics
+}
+
+fn collect_references<'a>(type_kind: &'a TypeKind, references: &mut Vec<(&'a str, &'a str)>) {
+    match type_kind {
+        TypeKind::Struct(name, path) | TypeKind::Enum(name, path) => {
+            references.push((name, path));
+        }
+        TypeKind::Vec(inner) | TypeKind::Option(inner) | TypeKind::Array(inner, _) => {
+            collect_references(inner, references);
+        }
+        TypeKind::HashMap(key, value) => {
+            collect_references(key, references);
+            collect_references(value, references);
+        }
+        TypeKind::Primitive(_) | TypeKind::String => {}
+    }
+}
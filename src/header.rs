@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+
+/// Marker that starts the first line of every header
+const GENERATED_MARKER: &str = "@generated by zorsh-gen-rs";
+
+/// Start of the header line holding the content hash
+const HASH_LABEL: &str = "Hash: sha256:";
+
+/// Header written at the top of generated files, marking them as not to be edited by hand.
+///
+/// Only files whose format has comments get one, so JSON output never does.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Header {
+    /// Write the header
+    pub enabled: bool,
+    /// Include the generator version
+    pub version: bool,
+    /// List the source files each module was generated from
+    pub sources: bool,
+    /// Include a hash of the rest of the file, so hand edits can be told apart from stale output
+    pub hash: bool,
+}
+
+impl Default for Header {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            version: true,
+            sources: true,
+            hash: false,
+        }
+    }
+}
+
+impl Header {
+    /// `content` with the header prepended, or unchanged if headers are disabled
+    /// or `path` is in a format without comments
+    pub fn apply(&self, path: &Path, content: &str, sources: &BTreeSet<PathBuf>) -> String {
+        let prefix = match comment_prefix(path) {
+            Some(prefix) if self.enabled => prefix,
+            _ => return content.to_string(),
+        };
+
+        let mut lines = vec![if self.version {
+            format!(
+                "{} {}. Do not edit by hand, changes will be overwritten.",
+                GENERATED_MARKER,
+                env!("CARGO_PKG_VERSION")
+            )
+        } else {
+            format!(
+                "{}. Do not edit by hand, changes will be overwritten.",
+                GENERATED_MARKER
+            )
+        }];
+        if self.sources && !sources.is_empty() {
+            let sources: Vec<_> = sources.iter().map(|path| display_path(path)).collect();
+            lines.push(format!("Sources: {}", sources.join(", ")));
+        }
+        if self.hash {
+            lines.push(format!("{}{}", HASH_LABEL, content_hash(content)));
+        }
+
+        let mut output: String = lines
+            .iter()
+            .map(|line| format!("{} {}\n", prefix, line))
+            .collect();
+        output.push('\n');
+        output.push_str(content);
+        output
+    }
+}
+
+/// Whether the file's body no longer matches the hash in its header, meaning it was edited
+/// after generation. Files without a hashed header are never reported as edited.
+pub fn is_hand_edited(path: &Path, content: &str) -> bool {
+    let Some(prefix) = comment_prefix(path) else {
+        return false;
+    };
+    if !content.starts_with(&format!("{} {}", prefix, GENERATED_MARKER)) {
+        return false;
+    }
+
+    let mut recorded_hash = None;
+    let mut rest = content;
+    while let Some(line) = rest.strip_prefix(prefix) {
+        let (line, remainder) = line.split_once('\n').unwrap_or((line, ""));
+        if let Some(hash) = line.trim().strip_prefix(HASH_LABEL) {
+            recorded_hash = Some(hash.to_string());
+        }
+        rest = remainder;
+    }
+    // The header is separated from the body by a blank line
+    let body = rest.strip_prefix('\n').unwrap_or(rest);
+
+    recorded_hash.is_some_and(|hash| hash != content_hash(body))
+}
+
+fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Line comment syntax of a generated file's format
+fn comment_prefix(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()? {
+        "ts" | "cts" | "mts" | "js" | "cjs" | "mjs" => Some("//"),
+        "py" => Some("#"),
+        _ => None,
+    }
+}
+
+/// Forward-slashed path without a leading `./`, so headers read the same on every platform
+fn display_path(path: &Path) -> String {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .map(|component| match component {
+            // Joined with the separator below
+            Component::RootDir => "".into(),
+            component => component.as_os_str().to_string_lossy(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -0,0 +1,97 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::dependency_resolver::{DependencyResolver, TypeDependencies};
+use crate::type_parser::{EnumInfo, StructInfo};
+
+/// Parsed and resolved types, in a form that can be saved as JSON and loaded again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeIr {
+    /// Version of zorsh-gen-rs that produced the IR
+    pub version: String,
+    /// Structs, sorted by full type path
+    pub structs: Vec<StructInfo>,
+    /// Enums, sorted by full type path
+    pub enums: Vec<EnumInfo>,
+    /// Dependency order and cross-module imports of the types above
+    pub dependencies: TypeDependencies,
+    /// Files each module's types were read from, keyed by module path
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sources: BTreeMap<String, BTreeSet<PathBuf>>,
+}
+
+impl TypeIr {
+    /// Resolves the dependencies of the given types and bundles them up
+    pub fn new(
+        structs: HashMap<String, StructInfo>,
+        enums: HashMap<String, EnumInfo>,
+    ) -> Result<Self> {
+        let dependencies = DependencyResolver::new(structs.clone(), enums.clone()).resolve()?;
+
+        let mut structs: Vec<_> = structs.into_values().collect();
+        structs.sort_by_key(|s| type_path(&s.module_path, &s.name));
+        let mut enums: Vec<_> = enums.into_values().collect();
+        enums.sort_by_key(|e| type_path(&e.module_path, &e.name));
+
+        Ok(Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            structs,
+            enums,
+            dependencies,
+            sources: BTreeMap::new(),
+        })
+    }
+
+    /// Records the files each module's types were read from
+    pub fn with_sources(mut self, sources: BTreeMap<String, BTreeSet<PathBuf>>) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Structs keyed by full type path, as the parser produces them
+    pub fn struct_map(&self) -> HashMap<String, StructInfo> {
+        self.structs
+            .iter()
+            .map(|s| (type_path(&s.module_path, &s.name), s.clone()))
+            .collect()
+    }
+
+    /// Enums keyed by full type path, as the parser produces them
+    pub fn enum_map(&self) -> HashMap<String, EnumInfo> {
+        self.enums
+            .iter()
+            .map(|e| (type_path(&e.module_path, &e.name), e.clone()))
+            .collect()
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)? + "\n")
+    }
+
+    /// Loads a previously dumped IR, re-resolving its dependencies so that
+    /// hand-edited or externally produced files stay consistent
+    pub fn from_json(json: &str) -> Result<Self> {
+        let ir: Self = serde_json::from_str(json).context("Failed to parse IR")?;
+        Ok(Self::new(ir.struct_map(), ir.enum_map())?.with_sources(ir.sources))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        Self::from_json(&json).with_context(|| format!("Invalid IR file: {}", path.display()))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_json()?)
+            .with_context(|| format!("Failed to write file: {}", path.display()))
+    }
+}
+
+fn type_path(module_path: &str, name: &str) -> String {
+    format!("{}::{}", module_path, name)
+}
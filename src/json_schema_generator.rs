@@ -0,0 +1,283 @@
+use super::dependency_resolver::TypeDependencies;
+use super::type_parser::{EnumInfo, FieldInfo, StructInfo, TypeKind};
+use crate::backend::{relative_path, Backend};
+use crate::{Config, OutputStructure};
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+
+/// Dialect declared by every generated document
+const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// File every type is written to when bundling
+pub const BUNDLE_FILE_NAME: &str = "schema.json";
+
+/// Generates JSON Schema documents describing the decoded (JSON) shape of each type
+pub struct JsonSchemaGenerator {
+    structs: HashMap<String, StructInfo>,
+    enums: HashMap<String, EnumInfo>,
+    config: Config,
+}
+
+impl JsonSchemaGenerator {
+    pub fn new(structs: HashMap<String, StructInfo>, enums: HashMap<String, EnumInfo>) -> Self {
+        Self {
+            structs,
+            enums,
+            config: Config::default(),
+        }
+    }
+
+    /// Use the given configuration instead of the defaults
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+}
+
+impl Backend for JsonSchemaGenerator {
+    fn generate_module(
+        &self,
+        current_module: &str,
+        dependencies: &TypeDependencies,
+    ) -> Result<String> {
+        let mut defs = Map::new();
+
+        for type_path in &dependencies.ordered_types {
+            if let Some(struct_info) = self.structs.get(type_path) {
+                if struct_info.module_path == current_module {
+                    defs.insert(
+                        struct_info.name.clone(),
+                        self.generate_struct(struct_info, Some(current_module)),
+                    );
+                }
+            } else if let Some(enum_info) = self.enums.get(type_path) {
+                if enum_info.module_path == current_module {
+                    defs.insert(
+                        enum_info.name.clone(),
+                        self.generate_enum(enum_info, Some(current_module)),
+                    );
+                }
+            }
+        }
+
+        document(defs)
+    }
+
+    fn module_file(&self, module: &str) -> PathBuf {
+        let separator = match self.config.output_structure {
+            OutputStructure::Nested => "/",
+            OutputStructure::Flat => "_",
+        };
+        PathBuf::from(format!("{}.schema.json", module.replace("::", separator)).to_lowercase())
+    }
+
+    /// JSON Schema has no imports; other modules are referenced by relative `$ref`s
+    fn render_import(&self, _module: &str, _from_module: &str, _type_names: &[String]) -> String {
+        String::new()
+    }
+
+    fn generate_files(
+        &self,
+        modules: &BTreeSet<String>,
+        dependencies: &TypeDependencies,
+    ) -> Result<BTreeMap<PathBuf, String>> {
+        let mut files = BTreeMap::new();
+
+        if self.config.bundle {
+            files.insert(
+                PathBuf::from(BUNDLE_FILE_NAME),
+                self.generate_bundle(dependencies)?,
+            );
+        } else {
+            for module in modules {
+                files.insert(
+                    self.module_file(module),
+                    self.generate_module(module, dependencies)?,
+                );
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+impl JsonSchemaGenerator {
+    /// Single document with every type, keyed by full type path
+    fn generate_bundle(&self, dependencies: &TypeDependencies) -> Result<String> {
+        let mut defs = Map::new();
+
+        for type_path in &dependencies.ordered_types {
+            if let Some(struct_info) = self.structs.get(type_path) {
+                defs.insert(type_path.clone(), self.generate_struct(struct_info, None));
+            } else if let Some(enum_info) = self.enums.get(type_path) {
+                defs.insert(type_path.clone(), self.generate_enum(enum_info, None));
+            }
+        }
+
+        document(defs)
+    }
+
+    fn generate_struct(&self, struct_info: &StructInfo, module: Option<&str>) -> Value {
+        let mut schema = self.fields_to_schema(&struct_info.fields, module);
+        insert_title(&mut schema, &struct_info.name);
+        schema
+    }
+
+    /// Enums decode to `{ "Variant": payload }`, so each variant is a single-key object
+    fn generate_enum(&self, enum_info: &EnumInfo, module: Option<&str>) -> Value {
+        let variants: Vec<_> = enum_info
+            .variants
+            .iter()
+            .map(|variant| {
+                let payload = match &variant.fields {
+                    None => json!({ "type": "object", "additionalProperties": false }),
+                    Some(fields) if fields.len() == 1 && fields[0].name.is_empty() => {
+                        // Tuple variant with single field
+                        self.type_to_schema(&fields[0].type_kind, module)
+                    }
+                    Some(fields) => self.fields_to_schema(fields, module),
+                };
+
+                json!({
+                    "type": "object",
+                    "properties": { variant.name.clone(): payload },
+                    "required": [variant.name],
+                    "additionalProperties": false,
+                })
+            })
+            .collect();
+
+        json!({ "title": enum_info.name, "oneOf": variants })
+    }
+
+    /// Named fields decode to an object, positional ones to a fixed-length array
+    fn fields_to_schema(&self, fields: &[FieldInfo], module: Option<&str>) -> Value {
+        if !fields.is_empty() && fields.iter().all(|field| field.name.is_empty()) {
+            let items: Vec<_> = fields
+                .iter()
+                .map(|field| self.type_to_schema(&field.type_kind, module))
+                .collect();
+            return json!({
+                "type": "array",
+                "prefixItems": items,
+                "minItems": fields.len(),
+                "maxItems": fields.len(),
+            });
+        }
+
+        let mut properties = Map::new();
+        for field in fields {
+            properties.insert(
+                field.name.clone(),
+                self.type_to_schema(&field.type_kind, module),
+            );
+        }
+        let required: Vec<_> = fields.iter().map(|field| field.name.clone()).collect();
+
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        })
+    }
+
+    fn type_to_schema(&self, type_kind: &TypeKind, module: Option<&str>) -> Value {
+        match type_kind {
+            TypeKind::Primitive(name) => primitive_schema(name),
+            TypeKind::String => json!({ "type": "string" }),
+            TypeKind::Struct(name, path) | TypeKind::Enum(name, path) => {
+                json!({ "$ref": self.type_ref(name, path, module) })
+            }
+            TypeKind::Vec(inner) => {
+                json!({ "type": "array", "items": self.type_to_schema(inner, module) })
+            }
+            TypeKind::HashMap(key, value) => {
+                let value = self.type_to_schema(value, module);
+                if matches!(**key, TypeKind::String) {
+                    json!({ "type": "object", "additionalProperties": value })
+                } else {
+                    // Only string keys fit in a JSON object; others decode to entry pairs
+                    json!({
+                        "type": "array",
+                        "items": {
+                            "type": "array",
+                            "prefixItems": [self.type_to_schema(key, module), value],
+                            "minItems": 2,
+                            "maxItems": 2,
+                        },
+                    })
+                }
+            }
+            TypeKind::Option(inner) => {
+                json!({ "anyOf": [self.type_to_schema(inner, module), { "type": "null" }] })
+            }
+            TypeKind::Array(inner, size) => json!({
+                "type": "array",
+                "items": self.type_to_schema(inner, module),
+                "minItems": size,
+                "maxItems": size,
+            }),
+        }
+    }
+
+    /// Reference to a type's definition: by full path in a bundle, otherwise by
+    /// name within the defining module's document
+    fn type_ref(&self, name: &str, path: &str, module: Option<&str>) -> String {
+        let Some(current_module) = module else {
+            return format!("#/$defs/{}", path);
+        };
+
+        let type_module = self
+            .structs
+            .get(path)
+            .map(|s| s.module_path.as_str())
+            .or_else(|| self.enums.get(path).map(|e| e.module_path.as_str()));
+
+        match type_module {
+            Some(type_module) if type_module != current_module => format!(
+                "{}#/$defs/{}",
+                relative_path(
+                    &self.module_file(current_module),
+                    &self.module_file(type_module)
+                ),
+                name
+            ),
+            _ => format!("#/$defs/{}", name),
+        }
+    }
+}
+
+fn document(defs: Map<String, Value>) -> Result<String> {
+    let document = json!({ "$schema": SCHEMA_DIALECT, "$defs": defs });
+    Ok(serde_json::to_string_pretty(&document)? + "\n")
+}
+
+fn insert_title(schema: &mut Value, title: &str) {
+    if let Value::Object(object) = schema {
+        let mut titled = Map::new();
+        titled.insert("title".to_string(), json!(title));
+        titled.append(object);
+        *object = titled;
+    }
+}
+
+/// Integers that fit in a double are numbers with their range; wider ones are
+/// decimal strings, as JavaScript decoders can't represent them exactly
+fn primitive_schema(name: &str) -> Value {
+    match name {
+        "u8" => json!({ "type": "integer", "minimum": 0, "maximum": u8::MAX }),
+        "u16" => json!({ "type": "integer", "minimum": 0, "maximum": u16::MAX }),
+        "u32" => json!({ "type": "integer", "minimum": 0, "maximum": u32::MAX }),
+        "i8" => json!({ "type": "integer", "minimum": i8::MIN, "maximum": i8::MAX }),
+        "i16" => json!({ "type": "integer", "minimum": i16::MIN, "maximum": i16::MAX }),
+        "i32" => json!({ "type": "integer", "minimum": i32::MIN, "maximum": i32::MAX }),
+        "u64" | "u128" => json!({ "type": "string", "pattern": "^[0-9]+$" }),
+        "i64" | "i128" => json!({ "type": "string", "pattern": "^-?[0-9]+$" }),
+        "f32" | "f64" => json!({ "type": "number" }),
+        "bool" => json!({ "type": "boolean" }),
+        _ => json!({}),
+    }
+}
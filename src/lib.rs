@@ -1,32 +1,114 @@
+pub mod backend;
+pub mod borsh_codec;
+pub mod borsh_js_generator;
+pub mod borsh_schema;
+pub mod borsh_schema_generator;
+pub mod borsh_schema_loader;
 pub mod code_generator;
+pub mod config_file;
 pub mod converter;
 pub mod dependency_resolver;
+pub mod fixtures;
+pub mod generation;
+pub mod header;
+pub mod ir;
+pub mod json_schema_generator;
+pub mod manifest;
+pub mod naming;
+pub mod near_abi;
+pub mod python_generator;
+pub mod size;
 pub mod source_loader;
+pub mod test_suites;
 pub mod type_parser;
 
 use anyhow::Result;
 use clap::ValueEnum;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 // Re-export main types for easier usage
+pub use backend::{Backend, BackendKind};
+pub use borsh_codec::BorshCodec;
+pub use borsh_js_generator::BorshJsGenerator;
+pub use borsh_schema::BorshSchemaContainer;
+pub use borsh_schema_generator::BorshSchemaGenerator;
+pub use borsh_schema_loader::BorshSchemaLoader;
 pub use code_generator::ZorshGenerator;
+pub use config_file::ConfigFile;
 pub use converter::ZorshConverter;
 pub use dependency_resolver::DependencyResolver;
-pub use source_loader::SourceLoader;
+pub use fixtures::{Fixture, FixtureGenerator};
+pub use generation::{Diagnostic, GeneratedFiles};
+pub use header::Header;
+pub use ir::TypeIr;
+pub use json_schema_generator::JsonSchemaGenerator;
+pub use naming::{Case, Naming};
+pub use near_abi::NearAbi;
+pub use python_generator::PythonGenerator;
+pub use size::{Size, SizeAnalyzer};
+pub use source_loader::{SourceFile, SourceLoader};
 pub use type_parser::TypeParser;
 
 /// Configuration options for the Zorsh generator
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     /// Only process structs with #[derive(BorshSerialize)]
     pub only_annotated: bool,
-    /// Skip files and directories matching these patterns
+    /// Skip files and directories matching these gitignore-style glob patterns,
+    /// relative to the input directory
     pub ignored_patterns: Vec<String>,
+    /// Only process files matching these gitignore-style glob patterns (all files when empty)
+    pub include_patterns: Vec<String>,
+    /// Also skip files excluded by `.gitignore`
+    pub respect_gitignore: bool,
     /// Output directory structure (flat or nested)
     pub output_structure: OutputStructure,
+    /// Code generation backend
+    pub backend: BackendKind,
+    /// Remove every file generated by a previous run before writing new ones
+    pub clean_output: bool,
+    /// Zorsh expressions to use in place of the named Rust types, keyed by
+    /// type name (`U128`) or full type path (`models::U128`)
+    pub type_overrides: BTreeMap<String, String>,
+    /// Write every module into a single file (json-schema backend)
+    pub bundle: bool,
+    /// `BorshSchemaContainer` JSON files to load alongside the Rust sources, keyed by
+    /// the module their types are placed in
+    pub borsh_schemas: BTreeMap<String, PathBuf>,
+    /// How TypeScript types are declared next to the Zorsh schemas
+    pub type_declarations: TypeDeclarations,
+    /// Write an `index.ts` re-exporting every module in each output directory
+    /// (TypeScript backends)
+    pub barrels: bool,
+    /// Extension of relative import specifiers (TypeScript backends)
+    pub import_extension: ImportExtension,
+    /// Module system of the generated TypeScript
+    pub module_format: ModuleFormat,
+    /// Names of the generated schemas, types and keys (zorsh backend)
+    pub naming: Naming,
+    /// Header written at the top of generated files
+    pub header: Header,
+    /// Emit an object per enum with variant constructors, type guards and `match`
+    /// (zorsh backend)
+    pub enum_helpers: bool,
+    /// Export the variant names and discriminants of each enum whose variants carry no
+    /// data as `{Enum}Values` and `{Enum}Discriminants` (zorsh backend)
+    pub enum_values: bool,
+    /// How enum variants with several unnamed fields are encoded (zorsh backend)
+    pub tuple_variants: TupleVariants,
+    /// Test framework of the round-trip `*.test.ts` written next to each module
+    /// (zorsh backend)
+    pub test_suites: TestSuites,
+    /// Export the serialized size of each fixed-size type as `{Type}FixedSize`
+    /// (zorsh backend)
+    pub size_constants: bool,
 }
 
-#[derive(Debug, Clone, ValueEnum)]
+#[derive(Debug, Clone, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputStructure {
     /// Maintain the same directory structure as input
     Nested,
@@ -34,6 +116,66 @@ pub enum OutputStructure {
     Flat,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TypeDeclarations {
+    /// `export type Foo = b.infer<typeof FooSchema>`
+    #[default]
+    Infer,
+    /// Explicit interfaces and union types in the schema file
+    Interfaces,
+    /// Explicit interfaces and union types in a separate `.types.ts` file
+    SeparateFile,
+}
+
+/// Extension written on relative import specifiers
+#[derive(Debug, Clone, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportExtension {
+    /// `./player`, for bundlers and `"moduleResolution": "bundler"`
+    #[default]
+    None,
+    /// `./player.js`, as `"moduleResolution": "NodeNext"` requires
+    Js,
+    /// `./player.ts`, for `allowImportingTsExtensions` and runtimes that run TypeScript directly
+    Ts,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleFormat {
+    /// ES modules in `.ts` files
+    #[default]
+    Esm,
+    /// CommonJS modules in `.cts` files, imported as `.cjs`/`.cts`
+    #[value(name = "commonjs")]
+    CommonJs,
+}
+
+/// Zorsh encoding of enum variants with several unnamed fields, like `Move(i32, i32)`
+#[derive(Debug, Clone, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TupleVariants {
+    /// `b.tuple(b.i32(), b.i32())`, decoded as `[number, number]`
+    #[default]
+    Tuple,
+    /// `b.struct({ _0: b.i32(), _1: b.i32() })`, decoded as `{ _0: number; _1: number }`
+    Struct,
+}
+
+/// Framework the generated round-trip tests are written for
+#[derive(Debug, Clone, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestSuites {
+    /// Don't write tests
+    #[default]
+    None,
+    /// Import `describe`, `expect` and `it` from `vitest`
+    Vitest,
+    /// Import `describe`, `expect` and `it` from `@jest/globals`
+    Jest,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -43,7 +185,25 @@ impl Default for Config {
                 "examples/".to_string(),
                 "target/".to_string(),
             ],
+            include_patterns: Vec::new(),
+            respect_gitignore: false,
             output_structure: OutputStructure::Nested,
+            backend: BackendKind::Zorsh,
+            clean_output: false,
+            type_overrides: BTreeMap::new(),
+            bundle: false,
+            borsh_schemas: BTreeMap::new(),
+            type_declarations: TypeDeclarations::Infer,
+            barrels: false,
+            import_extension: ImportExtension::None,
+            module_format: ModuleFormat::Esm,
+            naming: Naming::default(),
+            header: Header::default(),
+            enum_helpers: false,
+            enum_values: false,
+            tuple_variants: TupleVariants::Tuple,
+            test_suites: TestSuites::None,
+            size_constants: false,
         }
     }
 }
@@ -64,7 +224,36 @@ impl ZorshGen {
         converter.convert()
     }
 
-    /// Process a single Rust file and return the generated Zorsh code as a string
+    /// Generate code for the Rust files in input_path without writing anything
+    pub fn generate_path<P: AsRef<Path>>(&self, input_path: P) -> Result<GeneratedFiles> {
+        let sources = SourceLoader::new(input_path, self.config.ignored_patterns.clone())
+            .with_include_patterns(self.config.include_patterns.clone())
+            .with_gitignore(self.config.respect_gitignore)
+            .discover_rust_files()?;
+        self.generate(&sources)
+    }
+
+    /// Generate code for the given sources, returning file contents keyed by output path
+    pub fn generate(&self, sources: &[SourceFile]) -> Result<GeneratedFiles> {
+        generation::generate(sources, &self.config)
+    }
+
+    /// Parse and resolve the given sources without generating any code
+    pub fn parse(&self, sources: &[SourceFile]) -> Result<TypeIr> {
+        generation::build_ir(sources, &self.config).map(|(ir, _)| ir)
+    }
+
+    /// Generate code from previously parsed (or deserialized) types
+    pub fn generate_ir(&self, ir: &TypeIr) -> Result<GeneratedFiles> {
+        generation::generate_ir(ir, &self.config)
+    }
+
+    /// Generate code from previously parsed types with a backend of your own
+    pub fn generate_ir_with(&self, ir: &TypeIr, backend: &dyn Backend) -> Result<GeneratedFiles> {
+        generation::generate_ir_with(ir, &self.config, backend)
+    }
+
+    /// Process a single Rust file and return the generated code as a string
     pub fn convert_str(&self, rust_code: &str) -> Result<String> {
         let mut parser = TypeParser::new("root".to_string(), self.config.only_annotated);
         parser.parse_file(rust_code)?;
@@ -72,10 +261,13 @@ impl ZorshGen {
         let resolver = DependencyResolver::new(parser.structs.clone(), parser.enums.clone());
         let dependencies = resolver.resolve()?;
 
-        let generator = ZorshGenerator::new(parser.structs, parser.enums);
+        let backend = self
+            .config
+            .backend
+            .create(parser.structs, parser.enums, &self.config);
 
         // Since we're processing a single string, treat it as a single module
-        generator.generate_module("root", &dependencies)
+        backend.generate_module("root", &dependencies)
     }
 }
 
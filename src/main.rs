@@ -1,48 +1,640 @@
-use anyhow::Result;
-use clap::Parser;
-use zorsh_gen_rs::{Config, OutputStructure, ZorshConverter};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use zorsh_gen_rs::borsh_codec::{from_hex, to_hex};
+use zorsh_gen_rs::config_file::{CONFIG_FILE_NAME, CONFIG_TEMPLATE};
+use zorsh_gen_rs::fixtures::write_vectors;
+use zorsh_gen_rs::{
+    BackendKind, BorshCodec, Case, Config, ConfigFile, FixtureGenerator, ImportExtension,
+    ModuleFormat, NearAbi, OutputStructure, SizeAnalyzer, SourceLoader, TestSuites, TupleVariants,
+    TypeDeclarations, TypeIr, ZorshConverter, ZorshGen,
+};
 
 /// Zorsh Generator for Rust
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// Input directory containing Rust files
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    generate: GenerateArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Write a starter zorsh.toml to the current directory
+    Init {
+        /// Overwrite an existing zorsh.toml
+        #[arg(long)]
+        force: bool,
+    },
+    /// Generate Zorsh schemas for the borsh-serialized methods of a NEAR contract ABI
+    Abi {
+        /// ABI file produced by `cargo near abi`
+        #[arg(value_name = "ABI_FILE")]
+        abi_file: PathBuf,
+
+        /// File to write the schemas to (defaults to standard output)
+        #[arg(value_name = "OUTPUT_FILE")]
+        output_file: Option<PathBuf>,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+    /// Decode a Borsh-encoded value to JSON using the parsed Rust types
+    Decode {
+        /// Type of the value, by path (e.g., "models::Player") or by name if unambiguous
+        #[arg(long = "type", value_name = "TYPE")]
+        type_path: String,
+
+        /// File holding the encoded value
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// The file holds hex digits instead of raw bytes
+        #[arg(long)]
+        hex: bool,
+
+        #[command(flatten)]
+        types: TypesArgs,
+    },
+    /// Encode a JSON value to Borsh using the parsed Rust types
+    Encode {
+        /// Type of the value, by path (e.g., "models::Player") or by name if unambiguous
+        #[arg(long = "type", value_name = "TYPE")]
+        type_path: String,
+
+        /// JSON file holding the value, shaped as `decode` prints it
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// File to write the raw bytes to (defaults to hex on standard output)
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        #[command(flatten)]
+        types: TypesArgs,
+    },
+    /// Report the serialized size of each type: exact for fixed-size types, a minimum and a
+    /// formula for the others
+    Size {
+        /// Only report this type, by path (e.g., "models::Player") or by name if unambiguous
+        #[arg(long = "type", value_name = "TYPE")]
+        type_path: Option<String>,
+
+        #[command(flatten)]
+        types: TypesArgs,
+    },
+    /// Encode a directory of JSON samples to Borsh test vectors, and write a test checking
+    /// the generated schemas decode them
+    Fixtures {
+        /// Directory of JSON files named by the path of their type (e.g., "models/Player.json");
+        /// the `.bin` and `.hex` encodings are written next to them
+        #[arg(value_name = "FIXTURES_DIR")]
+        fixtures_dir: PathBuf,
+
+        /// Directory of the generated schemas, where fixtures.test.ts is written (defaults to
+        /// `output` from zorsh.toml)
+        #[arg(value_name = "OUTPUT_DIR")]
+        output_dir: Option<PathBuf>,
+
+        #[command(flatten)]
+        types: TypesArgs,
+    },
+}
+
+/// Where the `decode`, `encode`, `size` and `fixtures` commands find their types
+#[derive(Args, Debug)]
+struct TypesArgs {
+    /// Input directory containing Rust files (defaults to `input` from zorsh.toml)
+    #[arg(long, value_name = "DIR")]
+    input: Option<PathBuf>,
+
+    /// Read the types from a JSON file written by --emit-ir instead of Rust sources
+    #[arg(long, value_name = "FILE", conflicts_with = "input")]
+    from_ir: Option<PathBuf>,
+
+    #[command(flatten)]
+    config: ConfigArgs,
+}
+
+#[derive(Args, Debug)]
+struct GenerateArgs {
+    /// Input directory containing Rust files (defaults to `input` from zorsh.toml)
     #[arg(value_name = "INPUT_DIR")]
-    input_dir: String,
+    input_dir: Option<PathBuf>,
 
-    /// Output directory for generated TypeScript files
+    /// Output directory for generated TypeScript files (defaults to `output` from zorsh.toml)
     #[arg(value_name = "OUTPUT_DIR")]
-    output_dir: String,
+    output_dir: Option<PathBuf>,
+
+    #[command(flatten)]
+    config: ConfigArgs,
+
+    /// Remove all previously generated files before writing new ones
+    #[arg(long)]
+    clean: bool,
+
+    /// Verify the output directory is up to date instead of writing to it
+    #[arg(long)]
+    check: bool,
+
+    /// Write the parsed types as JSON to this file
+    #[arg(long, value_name = "FILE")]
+    emit_ir: Option<PathBuf>,
+
+    /// Generate from a JSON file written by --emit-ir instead of Rust sources; the only
+    /// positional argument is then OUTPUT_DIR
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["emit_ir", "output_dir"])]
+    from_ir: Option<PathBuf>,
+}
+
+/// Settings shared by generation and the `abi` command: the configuration file and the
+/// flags overriding it
+#[derive(Args, Debug)]
+struct ConfigArgs {
+    /// Read settings from this file instead of searching for zorsh.toml
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Ignore zorsh.toml and [package.metadata.zorsh]
+    #[arg(long, conflicts_with = "config")]
+    no_config: bool,
 
     /// Output structure: nested or flat
-    #[arg(long, value_enum, default_value_t = OutputStructure::Nested)]
-    output_structure: OutputStructure,
+    #[arg(long, value_enum)]
+    output_structure: Option<OutputStructure>,
+
+    /// Code generation backend
+    #[arg(long, value_enum)]
+    backend: Option<BackendKind>,
+
+    /// TypeScript types next to the schemas: inferred, explicit, or explicit in .types.ts files
+    #[arg(long, value_enum)]
+    type_declarations: Option<TypeDeclarations>,
 
     /// Only process structs and enums with #[derive(BorshSerialize)] or #[derive(BorshDeserialize)]
-    #[arg(long, default_value_t = true)]
-    only_annotated: bool,
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    only_annotated: Option<bool>,
 
-    /// Ignore files and directories matching these comma-separated patterns (e.g., "tests/,examples/,target/")
+    /// Ignore files and directories matching these comma-separated glob patterns (e.g., "tests/,examples/,target/")
     #[arg(long, value_delimiter = ',')]
-    ignored_patterns: Vec<String>,
+    ignored_patterns: Option<Vec<String>>,
+
+    /// Only process files matching these comma-separated glob patterns (e.g., "src/models/**")
+    #[arg(long = "include", value_delimiter = ',')]
+    include_patterns: Option<Vec<String>>,
+
+    /// Skip files excluded by .gitignore
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    respect_gitignore: Option<bool>,
+
+    /// Write a do-not-edit header at the top of generated files
+    #[arg(long)]
+    header: bool,
+
+    /// Extension of relative import specifiers: none, js (for NodeNext) or ts
+    #[arg(long, value_enum)]
+    import_extension: Option<ImportExtension>,
+
+    /// Module system of the generated TypeScript: esm (.ts files) or commonjs (.cts files)
+    #[arg(long, value_enum)]
+    module_format: Option<ModuleFormat>,
+
+    /// Write an index.ts re-exporting every module in each output directory
+    #[arg(long)]
+    barrels: bool,
+
+    /// Emit variant constructors, type guards and a match helper for each enum
+    #[arg(long)]
+    enum_helpers: bool,
+
+    /// Export the variant names and discriminants of each enum without data as
+    /// {Enum}Values and {Enum}Discriminants constants
+    #[arg(long)]
+    enum_values: bool,
+
+    /// Write a round-trip test suite next to each module for this framework: vitest or jest
+    #[arg(long, value_enum)]
+    test_suites: Option<TestSuites>,
+
+    /// Export the serialized size of each fixed-size type as a {Type}FixedSize constant
+    #[arg(long)]
+    size_constants: bool,
+
+    /// Encoding of enum variants with several unnamed fields: tuple or struct (`_0`, `_1` keys)
+    #[arg(long, value_enum)]
+    tuple_variants: Option<TupleVariants>,
+
+    /// Name template of schema constants, e.g. "{name}Schema" ({Name} is the Rust type name)
+    #[arg(long)]
+    schema_name: Option<String>,
+
+    /// Name template of TypeScript types, e.g. "{Name}Codec"
+    #[arg(long)]
+    type_name: Option<String>,
+
+    /// Case of struct field keys
+    #[arg(long, value_enum)]
+    field_case: Option<Case>,
+
+    /// Case of enum variant keys
+    #[arg(long, value_enum)]
+    variant_case: Option<Case>,
+
+    /// Write every module into a single file (json-schema backend)
+    #[arg(long)]
+    bundle: bool,
+
+    /// Use a Zorsh expression for a Rust type (e.g., "U128=b.u128()"); may be repeated
+    #[arg(long = "type-override", value_name = "TYPE=EXPR")]
+    type_overrides: Vec<String>,
+
+    /// Load types from a BorshSchemaContainer JSON file into a module (e.g.,
+    /// "external::tokens=schemas/tokens.json"); may be repeated
+    #[arg(long = "borsh-schema", value_name = "MODULE=FILE")]
+    borsh_schemas: Vec<String>,
 }
 
 fn main() -> Result<()> {
     env_logger::init();
-    let args = Args::parse();
+    let cli = Cli::parse();
+
+    match cli.command {
+        Some(Command::Init { force }) => init(force),
+        Some(Command::Abi {
+            abi_file,
+            output_file,
+            config,
+        }) => abi(&abi_file, output_file.as_deref(), config),
+        Some(Command::Decode {
+            type_path,
+            file,
+            hex,
+            types,
+        }) => decode(&type_path, &file, hex, types),
+        Some(Command::Encode {
+            type_path,
+            file,
+            output,
+            types,
+        }) => encode(&type_path, &file, output.as_deref(), types),
+        Some(Command::Size { type_path, types }) => size(type_path.as_deref(), types),
+        Some(Command::Fixtures {
+            fixtures_dir,
+            output_dir,
+            types,
+        }) => fixtures(&fixtures_dir, output_dir, types),
+        None => generate(cli.generate),
+    }
+}
+
+fn init(force: bool) -> Result<()> {
+    let path = env::current_dir()?.join(CONFIG_FILE_NAME);
+    if path.exists() && !force {
+        bail!(
+            "{} already exists (use --force to overwrite it)",
+            path.display()
+        );
+    }
+
+    fs::write(&path, CONFIG_TEMPLATE)
+        .with_context(|| format!("Failed to write file: {}", path.display()))?;
+    println!("📝 Created {}", path.display());
+
+    Ok(())
+}
+
+fn abi(abi_file: &Path, output_file: Option<&Path>, args: ConfigArgs) -> Result<()> {
+    let (config, _, _) = configure(args)?;
+    let (output, diagnostics) = NearAbi::load(abi_file)?.generate(&config)?;
+    for diagnostic in &diagnostics {
+        eprintln!("warning: {}", diagnostic);
+    }
+    let output = config.header.apply(
+        output_file.unwrap_or(Path::new("abi.ts")),
+        &output,
+        &BTreeSet::from([abi_file.to_path_buf()]),
+    );
+
+    match output_file {
+        Some(path) => {
+            fs::write(path, output)
+                .with_context(|| format!("Failed to write file: {}", path.display()))?;
+            println!("🎉 Schemas generated successfully in: {}", path.display());
+        }
+        None => print!("{}", output),
+    }
+
+    Ok(())
+}
+
+fn decode(type_path: &str, file: &Path, hex: bool, types: TypesArgs) -> Result<()> {
+    let codec = load_codec(types)?;
+    let bytes =
+        fs::read(file).with_context(|| format!("Failed to read file: {}", file.display()))?;
+    let bytes = if hex {
+        from_hex(&String::from_utf8_lossy(&bytes))?
+    } else {
+        bytes
+    };
+
+    let value = codec.decode(type_path, &bytes)?;
+    println!("{}", serde_json::to_string_pretty(&value)?);
+
+    Ok(())
+}
+
+fn encode(type_path: &str, file: &Path, output: Option<&Path>, types: TypesArgs) -> Result<()> {
+    let codec = load_codec(types)?;
+    let json = fs::read_to_string(file)
+        .with_context(|| format!("Failed to read file: {}", file.display()))?;
+    let value = serde_json::from_str(&json)
+        .with_context(|| format!("Failed to parse JSON: {}", file.display()))?;
+
+    let bytes = codec.encode(type_path, &value)?;
+    match output {
+        Some(path) => fs::write(path, bytes)
+            .with_context(|| format!("Failed to write file: {}", path.display()))?,
+        None => println!("{}", to_hex(&bytes)),
+    }
+
+    Ok(())
+}
+
+fn size(type_path: Option<&str>, types: TypesArgs) -> Result<()> {
+    let (ir, config, _) = load_types(types)?;
+    let structs = ir.struct_map();
+    let enums = ir.enum_map();
+    let analyzer = SizeAnalyzer::new(&structs, &enums).with_config(&config);
+
+    let sizes = match type_path {
+        Some(type_path) => {
+            let type_path = BorshCodec::from_ir(&ir).resolve_type(type_path)?;
+            let size = analyzer.size_of(&type_path);
+            vec![(type_path, size)]
+        }
+        None => analyzer.sizes(),
+    };
+
+    let width = sizes.iter().map(|(path, _)| path.len()).max().unwrap_or(0);
+    for (type_path, size) in sizes {
+        match size {
+            Ok(size) => println!("{:width$}  {}", type_path, size),
+            Err(error) => println!("{:width$}  unknown: {}", type_path, error),
+        }
+    }
+
+    Ok(())
+}
+
+fn fixtures(fixtures_dir: &Path, output_dir: Option<PathBuf>, types: TypesArgs) -> Result<()> {
+    let (ir, config, file_output) = load_types(types)?;
+    let output_dir = output_dir.or(file_output).ok_or_else(|| {
+        anyhow!(
+            "No output directory given and no `output` in {}",
+            CONFIG_FILE_NAME
+        )
+    })?;
+
+    let generator = FixtureGenerator::new(&ir, config);
+    let fixtures = generator.load_dir(fixtures_dir)?;
+    write_vectors(fixtures_dir, &fixtures)?;
+
+    let test_file = generator.test_file();
+    let path = output_dir.join(&test_file);
+    fs::create_dir_all(&output_dir)
+        .with_context(|| format!("Failed to create directory: {}", output_dir.display()))?;
+    fs::write(&path, generator.test_suite(&test_file, &fixtures))
+        .with_context(|| format!("Failed to write file: {}", path.display()))?;
+
+    println!(
+        "🎉 Encoded {} fixture(s) in {}, tested by {}",
+        fixtures.len(),
+        fixtures_dir.display(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+fn load_codec(args: TypesArgs) -> Result<BorshCodec> {
+    let (ir, _, _) = load_types(args)?;
+    Ok(BorshCodec::from_ir(&ir))
+}
+
+/// The parsed types, with the configuration and output directory of the configuration file
+fn load_types(args: TypesArgs) -> Result<(TypeIr, Config, Option<PathBuf>)> {
+    let (config, file_input, file_output) = configure(args.config)?;
+
+    let ir = match args.from_ir {
+        Some(ir_path) => TypeIr::load(ir_path)?,
+        None => {
+            let input_dir = args.input.or(file_input).ok_or_else(|| {
+                anyhow!(
+                    "No input directory given and no `input` in {}",
+                    CONFIG_FILE_NAME
+                )
+            })?;
+            let sources = SourceLoader::new(&input_dir, config.ignored_patterns.clone())
+                .with_include_patterns(config.include_patterns.clone())
+                .with_gitignore(config.respect_gitignore)
+                .discover_rust_files()?;
+            ZorshGen::new(config.clone()).parse(&sources)?
+        }
+    };
+
+    Ok((ir, config, file_output))
+}
+
+/// The configuration, with the input and output directories of the configuration file
+fn load_config(
+    path: Option<&Path>,
+    no_config: bool,
+) -> Result<(Config, Option<PathBuf>, Option<PathBuf>)> {
+    let config_file = if no_config {
+        None
+    } else if let Some(path) = path {
+        Some(ConfigFile::load(path)?)
+    } else {
+        ConfigFile::discover(env::current_dir()?)?
+    };
+
+    Ok(match config_file {
+        Some(file) => {
+            log::info!("Using configuration from {}", file.path.display());
+            (file.config, file.input, file.output)
+        }
+        // Without a configuration file nothing is ignored unless asked for
+        None => (
+            Config {
+                ignored_patterns: Vec::new(),
+                ..Config::default()
+            },
+            None,
+            None,
+        ),
+    })
+}
+
+/// The configuration with the command line flags applied, and the input and output
+/// directories of the configuration file
+fn configure(args: ConfigArgs) -> Result<(Config, Option<PathBuf>, Option<PathBuf>)> {
+    let (mut config, file_input, file_output) =
+        load_config(args.config.as_deref(), args.no_config)?;
+
+    // Command line flags take precedence over the configuration file
+    if let Some(output_structure) = args.output_structure {
+        config.output_structure = output_structure;
+    }
+    if let Some(backend) = args.backend {
+        config.backend = backend;
+    }
+    if let Some(type_declarations) = args.type_declarations {
+        config.type_declarations = type_declarations;
+    }
+    if let Some(only_annotated) = args.only_annotated {
+        config.only_annotated = only_annotated;
+    }
+    if let Some(ignored_patterns) = args.ignored_patterns {
+        config.ignored_patterns = ignored_patterns;
+    }
+    if let Some(include_patterns) = args.include_patterns {
+        config.include_patterns = include_patterns;
+    }
+    if let Some(respect_gitignore) = args.respect_gitignore {
+        config.respect_gitignore = respect_gitignore;
+    }
+    if args.header {
+        config.header.enabled = true;
+    }
+    if let Some(import_extension) = args.import_extension {
+        config.import_extension = import_extension;
+    }
+    if let Some(module_format) = args.module_format {
+        config.module_format = module_format;
+    }
+    if args.barrels {
+        config.barrels = true;
+    }
+    if args.enum_helpers {
+        config.enum_helpers = true;
+    }
+    if args.enum_values {
+        config.enum_values = true;
+    }
+    if args.size_constants {
+        config.size_constants = true;
+    }
+    if let Some(tuple_variants) = args.tuple_variants {
+        config.tuple_variants = tuple_variants;
+    }
+    if let Some(test_suites) = args.test_suites {
+        config.test_suites = test_suites;
+    }
+    if let Some(schema_name) = args.schema_name {
+        config.naming.schema_name = schema_name;
+    }
+    if let Some(type_name) = args.type_name {
+        config.naming.type_name = type_name;
+    }
+    if let Some(field_case) = args.field_case {
+        config.naming.field_case = field_case;
+    }
+    if let Some(variant_case) = args.variant_case {
+        config.naming.variant_case = variant_case;
+    }
+    if args.bundle {
+        config.bundle = true;
+    }
+    for type_override in &args.type_overrides {
+        let (name, expr) = type_override.split_once('=').ok_or_else(|| {
+            anyhow!(
+                "Invalid type override (expected TYPE=EXPR): {}",
+                type_override
+            )
+        })?;
+        config
+            .type_overrides
+            .insert(name.trim().to_string(), expr.trim().to_string());
+    }
+    for borsh_schema in &args.borsh_schemas {
+        let (module, file) = borsh_schema.split_once('=').ok_or_else(|| {
+            anyhow!(
+                "Invalid borsh schema (expected MODULE=FILE): {}",
+                borsh_schema
+            )
+        })?;
+        config
+            .borsh_schemas
+            .insert(module.trim().to_string(), PathBuf::from(file.trim()));
+    }
+
+    Ok((config, file_input, file_output))
+}
+
+fn generate(args: GenerateArgs) -> Result<()> {
+    let (mut config, file_input, file_output) = configure(args.config)?;
+    if args.clean {
+        config.clean_output = true;
+    }
+
+    let (input_dir, output_dir) = match &args.from_ir {
+        // There is no input directory, so a single positional argument is the output
+        Some(_) => (None, args.output_dir.or(args.input_dir)),
+        None => (args.input_dir, args.output_dir),
+    };
+    let output_dir = output_dir.or(file_output).ok_or_else(|| {
+        anyhow!(
+            "No output directory given and no `output` in {}",
+            CONFIG_FILE_NAME
+        )
+    })?;
 
-    let config = Config {
-        only_annotated: args.only_annotated,
-        ignored_patterns: args.ignored_patterns,
-        output_structure: args.output_structure,
+    let converter = match args.from_ir {
+        Some(ir_path) => ZorshConverter::from_ir(&ir_path, &output_dir, config),
+        None => {
+            let input_dir = input_dir.or(file_input).ok_or_else(|| {
+                anyhow!(
+                    "No input directory given and no `input` in {}",
+                    CONFIG_FILE_NAME
+                )
+            })?;
+            let converter = ZorshConverter::new(&input_dir, &output_dir, config);
+            match &args.emit_ir {
+                Some(ir_path) => converter.with_ir_output(ir_path),
+                None => converter,
+            }
+        }
     };
+    if args.check {
+        let problems = converter.check()?;
+        if !problems.is_empty() {
+            for problem in &problems {
+                eprintln!("{}", problem);
+            }
+            bail!(
+                "{} generated file(s) in {} are not up to date",
+                problems.len(),
+                output_dir.display()
+            );
+        }
+        println!(
+            "✅ Generated files are up to date in: {}",
+            output_dir.display()
+        );
+        return Ok(());
+    }
 
-    let converter = ZorshConverter::new(&args.input_dir, &args.output_dir, config);
     converter.convert()?;
 
     println!(
-        "🎉 Zorsh TypeScript schemas generated successfully in: {}",
-        args.output_dir
+        "🎉 Schemas generated successfully in: {}",
+        output_dir.display()
     );
 
     Ok(())
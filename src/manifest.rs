@@ -0,0 +1,77 @@
+use anyhow::{Context, Result};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest file written to the output directory
+pub const MANIFEST_FILE_NAME: &str = ".zorsh-manifest.json";
+
+/// Record of the files a previous run generated, so they can be cleaned up later
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Version of zorsh-gen-rs that wrote the manifest
+    pub version: String,
+    /// Generated files, relative to the output directory
+    pub files: BTreeSet<PathBuf>,
+}
+
+impl Manifest {
+    pub fn new(files: BTreeSet<PathBuf>) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            files,
+        }
+    }
+
+    /// Reads the manifest from `output_dir`, returning an empty one if there is none
+    pub fn load(output_dir: &Path) -> Result<Self> {
+        let path = output_dir.join(MANIFEST_FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse manifest: {}", path.display()))
+    }
+
+    pub fn save(&self, output_dir: &Path) -> Result<()> {
+        let path = output_dir.join(MANIFEST_FILE_NAME);
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&path, content + "\n")
+            .with_context(|| format!("Failed to write file: {}", path.display()))
+    }
+
+    /// Deletes the listed files that are not in `keep`, along with any
+    /// directories that are left empty
+    pub fn remove_files(&self, output_dir: &Path, keep: &BTreeSet<PathBuf>) -> Result<()> {
+        for file in self.files.difference(keep) {
+            // Never follow a manifest entry outside of the output directory
+            if file.is_absolute() || file.components().any(|c| c.as_os_str() == "..") {
+                continue;
+            }
+
+            let path = output_dir.join(file);
+            if !path.is_file() {
+                continue;
+            }
+
+            debug!("Removing stale file: {}", path.display());
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove file: {}", path.display()))?;
+
+            let mut dir = path.parent();
+            while let Some(current) = dir {
+                if current == output_dir || fs::remove_dir(current).is_err() {
+                    break;
+                }
+                dir = current.parent();
+            }
+        }
+
+        Ok(())
+    }
+}
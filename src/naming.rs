@@ -0,0 +1,128 @@
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use heck::{ToLowerCamelCase, ToPascalCase, ToSnakeCase};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Case conversion applied to generated object keys
+#[derive(Debug, Clone, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Case {
+    /// Keep the Rust name
+    #[default]
+    Preserve,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `PascalCase`
+    Pascal,
+}
+
+impl Case {
+    pub fn apply(&self, name: &str) -> String {
+        // Leading underscores (`_0`, `_private`) are kept, as the conversion would drop them
+        let words = name.trim_start_matches('_');
+        let underscores = &name[..name.len() - words.len()];
+
+        let words = match self {
+            Case::Preserve => words.to_string(),
+            Case::Camel => words.to_lower_camel_case(),
+            Case::Snake => words.to_snake_case(),
+            Case::Pascal => words.to_pascal_case(),
+        };
+        format!("{}{}", underscores, words)
+    }
+
+    /// Fails if two of the names of `owner` would get the same key
+    fn check_unique<'a>(
+        &self,
+        owner: &str,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<()> {
+        let mut keys = HashMap::new();
+        for name in names {
+            let key = self.apply(name);
+            if let Some(other) = keys.insert(key.clone(), name) {
+                bail!(
+                    "`{}` and `{}` of `{}` would both be keyed `{}`, choose another case",
+                    other,
+                    name,
+                    owner,
+                    key
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Names given to the generated schemas, types and keys.
+///
+/// Templates replace `{Name}` with the Rust type name and `{name}` with the same in camelCase.
+/// The Borsh wire format doesn't encode names, so none of these affect compatibility.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Naming {
+    /// Template for schema constants
+    pub schema_name: String,
+    /// Template for TypeScript types
+    pub type_name: String,
+    /// Case of struct field keys
+    pub field_case: Case,
+    /// Case of enum variant keys
+    pub variant_case: Case,
+}
+
+impl Default for Naming {
+    fn default() -> Self {
+        Self {
+            schema_name: "{Name}Schema".to_string(),
+            type_name: "{Name}".to_string(),
+            field_case: Case::Preserve,
+            variant_case: Case::Preserve,
+        }
+    }
+}
+
+impl Naming {
+    pub fn schema_name(&self, name: &str) -> String {
+        fill_template(&self.schema_name, name)
+    }
+
+    pub fn type_name(&self, name: &str) -> String {
+        fill_template(&self.type_name, name)
+    }
+
+    pub fn field_key(&self, name: &str) -> String {
+        self.field_case.apply(name)
+    }
+
+    pub fn variant_key(&self, name: &str) -> String {
+        self.variant_case.apply(name)
+    }
+
+    /// Fails if two fields of `owner` would get the same key
+    pub fn check_field_keys<'a>(
+        &self,
+        owner: &str,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<()> {
+        self.field_case.check_unique(owner, names)
+    }
+
+    /// Fails if two variants of `owner` would get the same key
+    pub fn check_variant_keys<'a>(
+        &self,
+        owner: &str,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<()> {
+        self.variant_case.check_unique(owner, names)
+    }
+}
+
+fn fill_template(template: &str, name: &str) -> String {
+    template
+        .replace("{Name}", name)
+        .replace("{name}", &name.to_lower_camel_case())
+}
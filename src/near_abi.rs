@@ -0,0 +1,183 @@
+use anyhow::{bail, Context, Result};
+use heck::ToUpperCamelCase;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::backend::Backend;
+use crate::borsh_schema::{BorshSchemaContainer, UNIT_DECLARATION};
+use crate::borsh_schema_loader::BorshSchemaLoader;
+use crate::code_generator::ZorshGenerator;
+use crate::generation::Diagnostic;
+use crate::ir::TypeIr;
+use crate::type_parser::{Docs, FieldInfo, StructInfo};
+use crate::{Config, TypeDeclarations};
+
+/// Module the contract's types are generated into
+const ABI_MODULE: &str = "abi";
+
+/// ABI file produced by `cargo near abi`; only the parts needed for generation are read
+#[derive(Debug, Clone, Deserialize)]
+pub struct NearAbi {
+    pub schema_version: String,
+    pub body: AbiBody,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbiBody {
+    pub functions: Vec<AbiFunction>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbiFunction {
+    pub name: String,
+    #[serde(default)]
+    pub params: Option<AbiParameters>,
+    #[serde(default)]
+    pub result: Option<AbiType>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "serialization_type", rename_all = "lowercase")]
+pub enum AbiParameters {
+    Json { args: Vec<serde_json::Value> },
+    Borsh { args: Vec<AbiBorshParameter> },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbiBorshParameter {
+    pub name: String,
+    pub type_schema: BorshSchemaContainer,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "serialization_type", rename_all = "lowercase")]
+pub enum AbiType {
+    Json { type_schema: serde_json::Value },
+    Borsh { type_schema: BorshSchemaContainer },
+}
+
+impl NearAbi {
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("Failed to parse NEAR ABI")
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        Self::from_json(&json).with_context(|| format!("Invalid ABI file: {}", path.display()))
+    }
+
+    /// Generates a Zorsh module with the contract's types, plus `{Function}ArgsSchema`
+    /// and `{Function}ResultSchema` for each borsh-serialized function.
+    ///
+    /// JSON-serialized arguments and results are skipped with a diagnostic.
+    pub fn generate(&self, config: &Config) -> Result<(String, Vec<Diagnostic>)> {
+        // The module is a single file, so there is nowhere to put the declarations
+        if config.type_declarations == TypeDeclarations::SeparateFile {
+            bail!(
+                "Type declarations in a separate file aren't supported for ABIs, use `interfaces`"
+            );
+        }
+
+        let mut loader = BorshSchemaLoader::new(ABI_MODULE.to_string());
+        let mut args_structs = Vec::new();
+        let mut results = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        for function in &self.body.functions {
+            let type_name = function.name.to_upper_camel_case();
+
+            match &function.params {
+                None => {}
+                Some(AbiParameters::Json { .. }) => diagnostics.push(Diagnostic {
+                    location: function.name.clone(),
+                    message: "arguments are JSON-serialized, skipping them".to_string(),
+                }),
+                Some(AbiParameters::Borsh { args }) => {
+                    let mut fields = Vec::new();
+                    for arg in args {
+                        let type_kind = loader.load_type(&arg.type_schema).with_context(|| {
+                            format!("Invalid type of `{}` in `{}`", arg.name, function.name)
+                        })?;
+                        fields.push(FieldInfo {
+                            name: arg.name.clone(),
+                            type_kind,
+                            docs: Docs::default(),
+                        });
+                    }
+                    args_structs.push((
+                        &function.name,
+                        StructInfo {
+                            name: format!("{}Args", type_name),
+                            module_path: ABI_MODULE.to_string(),
+                            fields,
+                            docs: Docs::default(),
+                        },
+                    ));
+                }
+            }
+
+            match &function.result {
+                None => {}
+                Some(AbiType::Json { .. }) => diagnostics.push(Diagnostic {
+                    location: function.name.clone(),
+                    message: "result is JSON-serialized, skipping it".to_string(),
+                }),
+                Some(AbiType::Borsh { type_schema })
+                    if type_schema.declaration == UNIT_DECLARATION => {}
+                Some(AbiType::Borsh { type_schema }) => {
+                    let type_kind = loader
+                        .load_type(type_schema)
+                        .with_context(|| format!("Invalid result type of `{}`", function.name))?;
+                    results.push((&function.name, format!("{}Result", type_name), type_kind));
+                }
+            }
+        }
+
+        // The generated names must not replace a contract type or each other
+        let mut generated: HashMap<&str, String> = HashMap::new();
+        let sources = args_structs
+            .iter()
+            .map(|(function, struct_info)| {
+                (
+                    &struct_info.name,
+                    format!("the arguments of `{}`", function),
+                )
+            })
+            .chain(
+                results
+                    .iter()
+                    .map(|(function, name, _)| (name, format!("the result of `{}`", function))),
+            );
+        for (name, source) in sources {
+            let path = format!("{}::{}", ABI_MODULE, name);
+            if loader.structs.contains_key(&path) || loader.enums.contains_key(&path) {
+                bail!("`{}` is both a contract type and {}", name, source);
+            }
+            if let Some(existing) = generated.insert(name, source.clone()) {
+                bail!("`{}` is both {} and {}", name, existing, source);
+            }
+        }
+
+        let mut structs = loader.structs;
+        for (_, struct_info) in args_structs {
+            structs.insert(format!("{}::{}", ABI_MODULE, struct_info.name), struct_info);
+        }
+        let ir = TypeIr::new(structs, loader.enums)?;
+        let generator =
+            ZorshGenerator::new(ir.struct_map(), ir.enum_map()).with_config(config.clone());
+
+        let mut output = generator.generate_module(ABI_MODULE, &ir.dependencies)?;
+
+        // Results aren't named types, so they are appended as plain schemas
+        for (_, name, type_kind) in &results {
+            output.push_str(&generator.generate_alias(name, type_kind));
+            output.push('\n');
+        }
+
+        Ok((output, diagnostics))
+    }
+}
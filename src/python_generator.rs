@@ -0,0 +1,424 @@
+use super::dependency_resolver::TypeDependencies;
+use super::type_parser::{EnumInfo, FieldInfo, StructInfo, TypeKind};
+use crate::backend::Backend;
+use crate::{Config, OutputStructure};
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+
+/// Python keywords that can't be used as dataclass field names
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Generates Python modules with `borsh-construct` schemas and dataclass type hints
+pub struct PythonGenerator {
+    structs: HashMap<String, StructInfo>,
+    enums: HashMap<String, EnumInfo>,
+    config: Config,
+}
+
+/// Names a generated module needs from `borsh_construct` and `typing`
+#[derive(Default)]
+struct PythonImports {
+    borsh: BTreeSet<String>,
+    typing: BTreeSet<&'static str>,
+}
+
+impl PythonGenerator {
+    pub fn new(structs: HashMap<String, StructInfo>, enums: HashMap<String, EnumInfo>) -> Self {
+        Self {
+            structs,
+            enums,
+            config: Config::default(),
+        }
+    }
+
+    /// Use the given configuration instead of the defaults
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+}
+
+impl Backend for PythonGenerator {
+    fn generate_module(
+        &self,
+        current_module: &str,
+        dependencies: &TypeDependencies,
+    ) -> Result<String> {
+        let mut imports = PythonImports::default();
+        let mut schemas = Vec::new();
+        let mut classes = Vec::new();
+
+        // Schemas are plain values, so they have to follow dependency order
+        for type_path in &dependencies.ordered_types {
+            if let Some(struct_info) = self.structs.get(type_path) {
+                if struct_info.module_path == current_module {
+                    schemas.push(self.generate_struct(struct_info, &mut imports));
+                    classes.push(self.generate_struct_class(struct_info, &mut imports));
+                }
+            } else if let Some(enum_info) = self.enums.get(type_path) {
+                if enum_info.module_path == current_module {
+                    schemas.push(self.generate_enum(enum_info, &mut imports));
+                    classes.push(self.generate_enum_classes(enum_info, &mut imports));
+                }
+            }
+        }
+
+        let mut output = String::new();
+        output.push_str("from __future__ import annotations\n\n");
+        output.push_str("from dataclasses import dataclass\n");
+        if !imports.typing.is_empty() {
+            output.push_str(&format!(
+                "from typing import {}\n",
+                imports
+                    .typing
+                    .iter()
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        output.push('\n');
+        output.push_str(&format!(
+            "from borsh_construct import {}\n",
+            imports.borsh.iter().cloned().collect::<Vec<_>>().join(", ")
+        ));
+
+        // Add imports from other modules, as their own group
+        let mut local_imports = String::new();
+        for (module_path, type_names) in &dependencies.module_imports {
+            if module_path != current_module {
+                let type_names: Vec<_> = type_names.iter().cloned().collect();
+                local_imports.push_str(&self.render_import(
+                    current_module,
+                    module_path,
+                    &type_names,
+                ));
+            }
+        }
+        if !local_imports.is_empty() {
+            output.push('\n');
+            output.push_str(&local_imports);
+        }
+
+        for schema in schemas {
+            output.push('\n');
+            output.push_str(&schema);
+        }
+        for class in classes {
+            output.push_str("\n\n");
+            output.push_str(&class);
+        }
+
+        Ok(output)
+    }
+
+    fn module_file(&self, module: &str) -> PathBuf {
+        let parts: Vec<_> = module.split("::").map(|p| p.to_lowercase()).collect();
+        match self.config.output_structure {
+            OutputStructure::Nested if self.is_package(module) => {
+                PathBuf::from(parts.join("/")).join("__init__.py")
+            }
+            OutputStructure::Nested => PathBuf::from(format!("{}.py", parts.join("/"))),
+            OutputStructure::Flat => PathBuf::from(format!("{}.py", parts.join("_"))),
+        }
+    }
+
+    fn render_import(&self, module: &str, from_module: &str, type_names: &[String]) -> String {
+        let mut names = Vec::new();
+        for name in type_names {
+            names.push(name.clone());
+            names.push(format!("{}Schema", name));
+        }
+
+        format!(
+            "from {} import {}\n",
+            self.relative_import(module, from_module),
+            names.join(", ")
+        )
+    }
+
+    fn generate_files(
+        &self,
+        modules: &BTreeSet<String>,
+        dependencies: &TypeDependencies,
+    ) -> Result<BTreeMap<PathBuf, String>> {
+        let mut files = BTreeMap::new();
+        for module in modules {
+            files.insert(
+                self.module_file(module),
+                self.generate_module(module, dependencies)?,
+            );
+        }
+
+        // Every directory (including the output root) has to be a package for
+        // the relative imports between modules to work
+        let mut packages = BTreeSet::from([PathBuf::from("__init__.py")]);
+        for path in files.keys() {
+            for dir in path.ancestors().skip(1) {
+                if !dir.as_os_str().is_empty() {
+                    packages.insert(dir.join("__init__.py"));
+                }
+            }
+        }
+        for package in packages {
+            files.entry(package).or_default();
+        }
+
+        Ok(files)
+    }
+}
+
+impl PythonGenerator {
+    /// A module with generated submodules becomes a package (`<module>/__init__.py`)
+    fn is_package(&self, module: &str) -> bool {
+        let prefix = format!("{}::", module);
+        self.structs
+            .values()
+            .map(|s| &s.module_path)
+            .chain(self.enums.values().map(|e| &e.module_path))
+            .any(|module_path| module_path.starts_with(&prefix))
+    }
+
+    /// Relative import path of `to` from the module `from`, e.g. `..items`
+    fn relative_import(&self, from: &str, to: &str) -> String {
+        if let OutputStructure::Flat = self.config.output_structure {
+            return format!(".{}", to.replace("::", "_").to_lowercase());
+        }
+
+        let to_parts: Vec<_> = to.split("::").map(|p| p.to_lowercase()).collect();
+        let mut package: Vec<_> = from.split("::").map(|p| p.to_lowercase()).collect();
+        // The package containing a module is its parent, unless it is a package itself
+        if !self.is_package(from) {
+            package.pop();
+        }
+
+        let common = package
+            .iter()
+            .zip(&to_parts)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        format!(
+            "{}{}",
+            ".".repeat(1 + package.len() - common),
+            to_parts[common..].join(".")
+        )
+    }
+
+    fn generate_struct(&self, struct_info: &StructInfo, imports: &mut PythonImports) -> String {
+        imports.borsh.insert("CStruct".to_string());
+        format!(
+            "{}Schema = {}\n",
+            struct_info.name,
+            self.fields_to_cstruct(&struct_info.fields, 0, imports)
+        )
+    }
+
+    fn generate_enum(&self, enum_info: &EnumInfo, imports: &mut PythonImports) -> String {
+        imports.borsh.insert("Enum".to_string());
+        // `Enum(\n,` isn't valid Python, so an enum without variants only gets its name
+        if enum_info.variants.is_empty() {
+            return format!(
+                "{}Schema = Enum(enum_name=\"{}\")\n",
+                enum_info.name, enum_info.name
+            );
+        }
+        let mut variants = Vec::new();
+
+        for variant in &enum_info.variants {
+            let variant_schema = match &variant.fields {
+                None => format!("    \"{}\"", variant.name),
+                Some(fields) if fields.iter().all(|field| field.name.is_empty()) => {
+                    imports.borsh.insert("TupleStruct".to_string());
+                    let types: Vec<_> = fields
+                        .iter()
+                        .map(|field| self.type_to_construct(&field.type_kind, imports))
+                        .collect();
+                    format!(
+                        "    \"{}\" / TupleStruct({})",
+                        variant.name,
+                        types.join(", ")
+                    )
+                }
+                Some(fields) => {
+                    imports.borsh.insert("CStruct".to_string());
+                    format!(
+                        "    \"{}\" / {}",
+                        variant.name,
+                        self.fields_to_cstruct(fields, 1, imports)
+                    )
+                }
+            };
+            variants.push(variant_schema);
+        }
+
+        format!(
+            "{}Schema = Enum(\n{},\n    enum_name=\"{}\",\n)\n",
+            enum_info.name,
+            variants.join(",\n"),
+            enum_info.name
+        )
+    }
+
+    /// Renders a `CStruct(...)` call, indented by `depth` levels
+    fn fields_to_cstruct(
+        &self,
+        fields: &[FieldInfo],
+        depth: usize,
+        imports: &mut PythonImports,
+    ) -> String {
+        if fields.is_empty() {
+            return "CStruct()".to_string();
+        }
+
+        let indent = "    ".repeat(depth);
+        let fields: Vec<_> = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                format!(
+                    "{}    \"{}\" / {},\n",
+                    indent,
+                    field_name(index, field),
+                    self.type_to_construct(&field.type_kind, imports)
+                )
+            })
+            .collect();
+
+        format!("CStruct(\n{}{})", fields.concat(), indent)
+    }
+
+    fn generate_struct_class(
+        &self,
+        struct_info: &StructInfo,
+        imports: &mut PythonImports,
+    ) -> String {
+        self.dataclass(&struct_info.name, &struct_info.fields, imports)
+    }
+
+    /// One dataclass per variant, plus a `Union` alias naming the enum
+    fn generate_enum_classes(&self, enum_info: &EnumInfo, imports: &mut PythonImports) -> String {
+        // `Union[]` isn't valid Python; an enum without variants has no values
+        if enum_info.variants.is_empty() {
+            imports.typing.insert("NoReturn");
+            return format!("{} = NoReturn\n", enum_info.name);
+        }
+        imports.typing.insert("Union");
+        let mut classes = Vec::new();
+        let mut variant_names = Vec::new();
+
+        for variant in &enum_info.variants {
+            let class_name = format!("{}{}", enum_info.name, variant.name);
+            let fields = variant.fields.as_deref().unwrap_or_default();
+            classes.push(self.dataclass(&class_name, fields, imports));
+            variant_names.push(class_name);
+        }
+
+        format!(
+            "{}\n\n{} = Union[{}]\n",
+            classes.join("\n\n"),
+            enum_info.name,
+            variant_names.join(", ")
+        )
+    }
+
+    fn dataclass(&self, name: &str, fields: &[FieldInfo], imports: &mut PythonImports) -> String {
+        let mut output = format!("@dataclass\nclass {}:\n", name);
+        if fields.is_empty() {
+            output.push_str("    pass\n");
+        }
+
+        for (index, field) in fields.iter().enumerate() {
+            output.push_str(&format!(
+                "    {}: {}\n",
+                field_name(index, field),
+                self.type_to_hint(&field.type_kind, imports)
+            ));
+        }
+
+        output
+    }
+
+    fn type_to_construct(&self, type_kind: &TypeKind, imports: &mut PythonImports) -> String {
+        match type_kind {
+            TypeKind::Primitive(name) => {
+                let construct = primitive_construct(name);
+                imports.borsh.insert(construct.clone());
+                construct
+            }
+            TypeKind::String => {
+                imports.borsh.insert("String".to_string());
+                "String".to_string()
+            }
+            TypeKind::Struct(name, _) | TypeKind::Enum(name, _) => format!("{}Schema", name),
+            TypeKind::Vec(inner) => {
+                imports.borsh.insert("Vec".to_string());
+                format!("Vec({})", self.type_to_construct(inner, imports))
+            }
+            TypeKind::HashMap(key, value) => {
+                imports.borsh.insert("HashMap".to_string());
+                format!(
+                    "HashMap({}, {})",
+                    self.type_to_construct(key, imports),
+                    self.type_to_construct(value, imports)
+                )
+            }
+            TypeKind::Option(inner) => {
+                imports.borsh.insert("Option".to_string());
+                format!("Option({})", self.type_to_construct(inner, imports))
+            }
+            TypeKind::Array(inner, size) => {
+                format!("{}[{}]", self.type_to_construct(inner, imports), size)
+            }
+        }
+    }
+
+    #[allow(clippy::only_used_in_recursion)] // Parameter is essential for recursive calls
+    fn type_to_hint(&self, type_kind: &TypeKind, imports: &mut PythonImports) -> String {
+        match type_kind {
+            TypeKind::Primitive(name) if name == "bool" => "bool".to_string(),
+            TypeKind::Primitive(name) if name.starts_with('f') => "float".to_string(),
+            TypeKind::Primitive(_) => "int".to_string(),
+            TypeKind::String => "str".to_string(),
+            TypeKind::Struct(name, _) | TypeKind::Enum(name, _) => name.clone(),
+            TypeKind::Vec(inner) | TypeKind::Array(inner, _) => {
+                format!("list[{}]", self.type_to_hint(inner, imports))
+            }
+            TypeKind::HashMap(key, value) => format!(
+                "dict[{}, {}]",
+                self.type_to_hint(key, imports),
+                self.type_to_hint(value, imports)
+            ),
+            TypeKind::Option(inner) => {
+                imports.typing.insert("Optional");
+                format!("Optional[{}]", self.type_to_hint(inner, imports))
+            }
+        }
+    }
+}
+
+/// Python name of a field, shared by the `CStruct` and the dataclass so they agree:
+/// positional fields are numbered and keywords get a trailing underscore
+fn field_name(index: usize, field: &FieldInfo) -> String {
+    if field.name.is_empty() {
+        format!("_{}", index)
+    } else if PYTHON_KEYWORDS.contains(&field.name.as_str()) {
+        format!("{}_", field.name)
+    } else {
+        field.name.clone()
+    }
+}
+
+/// Name of the `borsh_construct` value for a Rust primitive, e.g. `u32` -> `U32`
+fn primitive_construct(name: &str) -> String {
+    match name {
+        "bool" => "Bool".to_string(),
+        _ => name.to_uppercase(),
+    }
+}
@@ -0,0 +1,336 @@
+use anyhow::{anyhow, bail, Result};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::borsh_schema;
+use crate::code_generator::{is_tuple, type_override};
+use crate::type_parser::{EnumInfo, FieldInfo, StructInfo, TypeKind};
+use crate::Config;
+
+/// Number of bytes a type takes once Borsh-serialized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Size {
+    /// Every value takes exactly this many bytes
+    Fixed(usize),
+    /// Values take at least `min` bytes. `formula` gives the exact count in terms of the
+    /// value, like `12 + len(name) + len(items) * 8`, where `len` is a string's byte
+    /// length or a collection's element count.
+    Variable { min: usize, formula: String },
+}
+
+impl Size {
+    pub fn is_fixed(&self) -> bool {
+        matches!(self, Size::Fixed(_))
+    }
+
+    pub fn min(&self) -> usize {
+        match self {
+            Size::Fixed(size) => *size,
+            Size::Variable { min, .. } => *min,
+        }
+    }
+}
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Size::Fixed(size) => write!(f, "{}", bytes(*size)),
+            Size::Variable { min, formula } => {
+                write!(f, "at least {}: {}", bytes(*min), formula)
+            }
+        }
+    }
+}
+
+fn bytes(count: usize) -> String {
+    if count == 1 {
+        "1 byte".to_string()
+    } else {
+        format!("{} bytes", count)
+    }
+}
+
+/// A size as a constant plus variable terms, each with the least it can add
+#[derive(Default)]
+struct Sum {
+    constant: usize,
+    terms: Vec<(String, usize)>,
+}
+
+impl Sum {
+    fn fixed(size: usize) -> Self {
+        Self {
+            constant: size,
+            terms: Vec::new(),
+        }
+    }
+
+    fn term(text: String, min: usize) -> Self {
+        Self {
+            constant: 0,
+            terms: vec![(text, min)],
+        }
+    }
+
+    fn add(&mut self, other: Sum) {
+        self.constant += other.constant;
+        self.terms.extend(other.terms);
+    }
+
+    fn fixed_size(&self) -> Option<usize> {
+        self.terms.is_empty().then_some(self.constant)
+    }
+
+    fn min(&self) -> usize {
+        self.constant + self.terms.iter().map(|(_, min)| min).sum::<usize>()
+    }
+
+    fn formula(&self) -> String {
+        let mut parts = Vec::new();
+        if self.constant > 0 || self.terms.is_empty() {
+            parts.push(self.constant.to_string());
+        }
+        parts.extend(self.terms.iter().map(|(text, _)| text.clone()));
+        parts.join(" + ")
+    }
+
+    fn into_size(self) -> Size {
+        match self.fixed_size() {
+            Some(size) => Size::Fixed(size),
+            None => Size::Variable {
+                min: self.min(),
+                formula: self.formula(),
+            },
+        }
+    }
+}
+
+/// Works out the serialized size of types from their definitions, for estimating
+/// storage costs.
+///
+/// Formulas name the parts of a value by their path from it: `name` is a field,
+/// `items[i]` an element of a collection, summed over `i` with `sum(..)`, and `status` an enum
+/// whose size depends on its variant, given by a `match`.
+pub struct SizeAnalyzer<'a> {
+    structs: &'a HashMap<String, StructInfo>,
+    enums: &'a HashMap<String, EnumInfo>,
+    config: Option<&'a Config>,
+}
+
+impl<'a> SizeAnalyzer<'a> {
+    pub fn new(
+        structs: &'a HashMap<String, StructInfo>,
+        enums: &'a HashMap<String, EnumInfo>,
+    ) -> Self {
+        Self {
+            structs,
+            enums,
+            config: None,
+        }
+    }
+
+    /// Treat the types overridden in `config` as unknown, as their Rust definitions
+    /// may not be what's serialized
+    pub fn with_config(mut self, config: &'a Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Size of the struct or enum at `type_path`
+    pub fn size_of(&self, type_path: &str) -> Result<Size> {
+        self.type_size(&TypeKind::named(type_path, self.structs))
+    }
+
+    /// Size of a value of `type_kind`
+    pub fn type_size(&self, type_kind: &TypeKind) -> Result<Size> {
+        Ok(self.sum(type_kind, "", &mut Vec::new())?.into_size())
+    }
+
+    /// Sizes of every struct and enum, by type path
+    pub fn sizes(&self) -> Vec<(String, Result<Size>)> {
+        let mut type_paths: Vec<_> = self.structs.keys().chain(self.enums.keys()).collect();
+        type_paths.sort();
+        type_paths
+            .into_iter()
+            .map(|type_path| (type_path.clone(), self.size_of(type_path)))
+            .collect()
+    }
+
+    /// Size of the value at `path`. `stack` holds the types being sized, so a type
+    /// containing itself ends in a `size(..)` term instead of recursing forever.
+    fn sum(&self, type_kind: &TypeKind, path: &str, stack: &mut Vec<String>) -> Result<Sum> {
+        match type_kind {
+            TypeKind::Primitive(name) => match borsh_schema::primitive_size(name) {
+                Some(size) => Ok(Sum::fixed(size.into())),
+                None => bail!("Unsupported primitive `{}`", name),
+            },
+            TypeKind::String => {
+                let mut sum = Sum::fixed(4);
+                sum.add(Sum::term(format!("len({})", value(path)), 0));
+                Ok(sum)
+            }
+            TypeKind::Struct(name, type_path) | TypeKind::Enum(name, type_path) => {
+                if self
+                    .config
+                    .is_some_and(|config| type_override(config, name, type_path).is_some())
+                {
+                    bail!("The size of `{}` isn't known: it's overridden", name);
+                }
+                if stack.contains(type_path) {
+                    return Ok(Sum::term(format!("size({})", value(path)), 0));
+                }
+
+                stack.push(type_path.clone());
+                let sum = if let Some(struct_info) = self.structs.get(type_path) {
+                    self.fields_sum(&struct_info.fields, path, stack)
+                } else if let Some(enum_info) = self.enums.get(type_path) {
+                    self.enum_sum(enum_info, path, stack)
+                } else {
+                    Err(anyhow!(
+                        "The size of `{}` isn't known: it's not defined",
+                        name
+                    ))
+                };
+                stack.pop();
+
+                sum
+            }
+            TypeKind::Vec(inner) => {
+                let element = self.sum(inner, &element(path), stack)?;
+                let mut sum = Sum::fixed(4);
+                sum.add(elements(element, &format!("len({})", value(path))));
+                Ok(sum)
+            }
+            TypeKind::Array(inner, length) => {
+                let element = self.sum(inner, &element(path), stack)?;
+                match element.fixed_size() {
+                    Some(size) => Ok(Sum::fixed(size * length)),
+                    None => Ok(Sum::term(
+                        format!("sum({})", element.formula()),
+                        element.min() * length,
+                    )),
+                }
+            }
+            TypeKind::Option(inner) => {
+                let inner = self.sum(inner, path, stack)?;
+                let mut sum = Sum::fixed(1);
+                if inner.fixed_size() != Some(0) {
+                    sum.add(Sum::term(
+                        format!("({} ? {} : 0)", value(path), inner.formula()),
+                        0,
+                    ));
+                }
+                Ok(sum)
+            }
+            TypeKind::HashMap(key, value_kind) => {
+                let mut entry = self.sum(
+                    key,
+                    &format!("{}.keys[{}]", value(path), index(path)),
+                    stack,
+                )?;
+                entry.add(self.sum(
+                    value_kind,
+                    &format!("{}.values[{}]", value(path), index(path)),
+                    stack,
+                )?);
+
+                let mut sum = Sum::fixed(4);
+                sum.add(elements(entry, &format!("len({})", value(path))));
+                Ok(sum)
+            }
+        }
+    }
+
+    fn fields_sum(&self, fields: &[FieldInfo], path: &str, stack: &mut Vec<String>) -> Result<Sum> {
+        let mut sum = Sum::default();
+        for (index, field) in fields.iter().enumerate() {
+            let name = if field.name.is_empty() {
+                index.to_string()
+            } else {
+                field.name.clone()
+            };
+            sum.add(self.sum(&field.type_kind, &join(path, &name), stack)?);
+        }
+        Ok(sum)
+    }
+
+    /// The tag, and a payload that's fixed only when every variant's is the same size
+    fn enum_sum(&self, enum_info: &EnumInfo, path: &str, stack: &mut Vec<String>) -> Result<Sum> {
+        if enum_info.variants.is_empty() {
+            bail!("`{}` has no variants, so it has no values", enum_info.name);
+        }
+        let payloads = enum_info
+            .variants
+            .iter()
+            .map(|variant| {
+                let payload = match &variant.fields {
+                    None => Sum::default(),
+                    // A single unnamed field is the variant's value itself
+                    Some(fields) if is_tuple(fields) && fields.len() == 1 => {
+                        self.sum(&fields[0].type_kind, &join(path, "0"), stack)?
+                    }
+                    Some(fields) => self.fields_sum(fields, path, stack)?,
+                };
+                Ok((variant.name.as_str(), payload))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut sum = Sum::fixed(1);
+        let fixed: Vec<_> = payloads.iter().map(|(_, p)| p.fixed_size()).collect();
+        match fixed.first() {
+            Some(Some(size)) if fixed.iter().all(|other| *other == Some(*size)) => {
+                sum.add(Sum::fixed(*size));
+            }
+            _ => {
+                let min = payloads.iter().map(|(_, p)| p.min()).min().unwrap_or(0);
+                let arms: Vec<_> = payloads
+                    .iter()
+                    .map(|(name, payload)| format!("{} => {}", name, payload.formula()))
+                    .collect();
+                sum.add(Sum::term(
+                    format!("match {} {{ {} }}", value(path), arms.join(", ")),
+                    min,
+                ));
+            }
+        }
+        Ok(sum)
+    }
+}
+
+/// Size of a collection's elements, given the expression of their count
+fn elements(element: Sum, count: &str) -> Sum {
+    match element.fixed_size() {
+        Some(0) => Sum::default(),
+        Some(1) => Sum::term(count.to_string(), 0),
+        Some(size) => Sum::term(format!("{} * {}", count, size), 0),
+        None => Sum::term(format!("sum({})", element.formula()), 0),
+    }
+}
+
+/// Path of a part of the value at `path`
+fn join(path: &str, part: &str) -> String {
+    if path.is_empty() && !part.starts_with(|c: char| c.is_ascii_digit()) {
+        part.to_string()
+    } else {
+        format!("{}.{}", value(path), part)
+    }
+}
+
+/// Element of the collection at `path`, indexed by `i`, or `j` and so on when nested
+fn element(path: &str) -> String {
+    format!("{}[{}]", value(path), index(path))
+}
+
+fn index(path: &str) -> char {
+    let depth = path.matches('[').count() as u8;
+    (b'i' + depth.min(b'z' - b'i')) as char
+}
+
+/// The value at `path`, with `self` for the value being sized
+fn value(path: &str) -> &str {
+    if path.is_empty() {
+        "self"
+    } else {
+        path
+    }
+}
@@ -1,7 +1,8 @@
 use anyhow::{Context, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::WalkBuilder;
 use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
 
 pub struct SourceFile {
     pub path: PathBuf,
@@ -9,9 +10,22 @@ pub struct SourceFile {
     pub module_path: String,
 }
 
+impl SourceFile {
+    /// Creates a source that doesn't exist on disk, e.g. `("models::player", code)`
+    pub fn in_memory(module_path: &str, content: &str) -> Self {
+        Self {
+            path: PathBuf::from(format!("{}.rs", module_path.replace("::", "/"))),
+            content: content.to_string(),
+            module_path: module_path.to_string(),
+        }
+    }
+}
+
 pub struct SourceLoader {
     root_path: PathBuf,
     ignored_patterns: Vec<String>,
+    include_patterns: Vec<String>,
+    respect_gitignore: bool,
 }
 
 impl SourceLoader {
@@ -19,30 +33,81 @@ impl SourceLoader {
         Self {
             root_path: root_path.as_ref().to_path_buf(),
             ignored_patterns,
+            include_patterns: Vec::new(),
+            respect_gitignore: false,
         }
     }
 
-    fn is_ignored(&self, entry: &walkdir::DirEntry) -> bool {
-        let path = entry.path().to_string_lossy();
-        self.ignored_patterns
-            .iter()
-            .any(|pattern| path.contains(pattern))
+    /// Only load files matching at least one of these patterns (all files when empty)
+    pub fn with_include_patterns(mut self, include_patterns: Vec<String>) -> Self {
+        self.include_patterns = include_patterns;
+        self
+    }
+
+    /// Also skip anything excluded by `.gitignore` files
+    pub fn with_gitignore(mut self, respect_gitignore: bool) -> Self {
+        self.respect_gitignore = respect_gitignore;
+        self
+    }
+
+    /// Directory the sources are loaded from. Patterns are matched against it, so they
+    /// behave like a `.gitignore` in it
+    pub(crate) fn root_dir(&self) -> &Path {
+        if self.root_path.is_file() {
+            self.root_path.parent().unwrap_or_else(|| Path::new(""))
+        } else {
+            &self.root_path
+        }
+    }
+
+    /// Builds a gitignore-style matcher from a list of glob patterns
+    fn build_matcher(&self, patterns: &[String]) -> Result<Gitignore> {
+        let mut builder = GitignoreBuilder::new(self.root_dir());
+        for pattern in patterns {
+            builder
+                .add_line(None, pattern)
+                .with_context(|| format!("Invalid pattern: {}", pattern))?;
+        }
+        builder.build().context("Failed to build pattern matcher")
     }
 
     pub fn discover_rust_files(&self) -> Result<Vec<SourceFile>> {
+        let excluded = self.build_matcher(&self.ignored_patterns)?;
+        let included = self.build_matcher(&self.include_patterns)?;
         let mut files = Vec::new();
 
-        for entry in WalkDir::new(&self.root_path)
+        let walker = WalkBuilder::new(&self.root_path)
             .follow_links(true)
-            .into_iter()
-            .filter_entry(|e| !Self::is_hidden(e) && !self.is_ignored(e))
-        {
+            .hidden(true)
+            .ignore(false)
+            .git_global(false)
+            .git_ignore(self.respect_gitignore)
+            .git_exclude(self.respect_gitignore)
+            .parents(self.respect_gitignore)
+            .require_git(false)
+            .filter_entry(move |entry| {
+                entry.depth() == 0
+                    || !excluded
+                        .matched(entry.path(), entry.file_type().is_some_and(|t| t.is_dir()))
+                        .is_ignore()
+            })
+            .build();
+
+        for entry in walker {
             let entry = entry.context("Failed to read directory entry")?;
             if !Self::is_rust_file(entry.path()) {
                 continue;
             }
 
             let path = entry.path().to_path_buf();
+            if !self.include_patterns.is_empty()
+                && !included
+                    .matched_path_or_any_parents(&path, false)
+                    .is_ignore()
+            {
+                continue;
+            }
+
             let content = fs::read_to_string(&path)
                 .with_context(|| format!("Failed to read file: {}", path.display()))?;
 
@@ -58,14 +123,6 @@ impl SourceLoader {
         Ok(files)
     }
 
-    fn is_hidden(entry: &walkdir::DirEntry) -> bool {
-        entry
-            .file_name()
-            .to_str()
-            .map(|s| s.starts_with('.'))
-            .unwrap_or(false)
-    }
-
     fn is_rust_file(path: &Path) -> bool {
         path.extension()
             .and_then(|ext| ext.to_str())
@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::backend::typescript_import_specifier;
+use crate::code_generator::{is_tuple, type_override};
+use crate::type_parser::{EnumInfo, EnumVariant, StructInfo, TypeKind};
+use crate::{Config, TestSuites, TupleVariants};
+
+/// Fixed-size arrays longer than this are built with `Array.from` instead of written out
+const MAX_INLINE_ARRAY: usize = 4;
+
+/// Whether a sample fills every optional part of its type or leaves them out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fill {
+    /// Options are set, collections have an element and numbers are at their limits
+    Full,
+    /// Options are `null`, collections are empty and numbers are zero
+    Empty,
+}
+
+/// Why no sample can be built for a type
+enum NoSample {
+    /// The named type is overridden or unresolved, so its encoding isn't known
+    Unknown(String),
+    /// The type contains itself, which only a collection can end
+    Recursive,
+    /// The named enum has no variants, so it has no values
+    NoVariants(String),
+}
+
+/// Writes a module's `*.test.ts` file: sample values of each of its types, checked to
+/// survive a serialize/deserialize round trip through their schema
+pub(crate) struct TestSuiteGenerator<'a> {
+    structs: &'a HashMap<String, StructInfo>,
+    enums: &'a HashMap<String, EnumInfo>,
+    config: &'a Config,
+}
+
+impl<'a> TestSuiteGenerator<'a> {
+    pub fn new(
+        structs: &'a HashMap<String, StructInfo>,
+        enums: &'a HashMap<String, EnumInfo>,
+        config: &'a Config,
+    ) -> Self {
+        Self {
+            structs,
+            enums,
+            config,
+        }
+    }
+
+    /// Test suite for the types at `type_paths`, which are defined in `module_file`
+    pub fn generate(&self, test_file: &Path, module_file: &Path, type_paths: &[String]) -> String {
+        let mut imports = Vec::new();
+        let mut suites = Vec::new();
+
+        for type_path in type_paths {
+            let (name, samples) = if let Some(struct_info) = self.structs.get(type_path) {
+                (&struct_info.name, self.struct_samples(type_path))
+            } else if let Some(enum_info) = self.enums.get(type_path) {
+                (&enum_info.name, self.enum_samples(enum_info))
+            } else {
+                continue;
+            };
+
+            match samples {
+                Ok(samples) => {
+                    let schema_name = self.config.naming.schema_name(name);
+                    let type_name = self.config.naming.type_name(name);
+                    suites.push(suite(&type_name, &schema_name, &samples));
+                    imports.push(schema_name);
+                    imports.push(format!("type {}", type_name));
+                }
+                Err(NoSample::Unknown(unknown)) => {
+                    suites.push(format!("// {}\n", unknown_encoding(name, &unknown)))
+                }
+                Err(NoSample::Recursive) => suites.push(format!(
+                    "// {} is not tested: it contains itself outside of a collection\n",
+                    name
+                )),
+                Err(NoSample::NoVariants(empty)) => suites.push(format!(
+                    "// {} is not tested: `{}` has no variants to sample\n",
+                    name, empty
+                )),
+            }
+        }
+
+        let mut output = framework_import(self.config);
+        if !imports.is_empty() {
+            output.push_str(&format!(
+                "import {{ {} }} from '{}';\n",
+                imports.join(", "),
+                typescript_import_specifier(test_file, module_file, self.config)
+            ));
+        }
+        for suite in suites {
+            output.push('\n');
+            output.push_str(&suite);
+        }
+
+        output
+    }
+
+    /// A sample with every optional part filled and one with none, labelled `full` and `empty`
+    fn struct_samples(&self, type_path: &str) -> Result<Vec<(String, String)>, NoSample> {
+        let struct_info = &self.structs[type_path];
+        let type_kind = TypeKind::Struct(struct_info.name.clone(), type_path.to_string());
+
+        let full = self.sample(&type_kind, Fill::Full, &mut Vec::new())?;
+        let empty = self.sample(&type_kind, Fill::Empty, &mut Vec::new())?;
+        Ok(vec![
+            ("full".to_string(), full),
+            ("empty".to_string(), empty),
+        ])
+    }
+
+    /// A sample of each variant, labelled with the variant's name
+    fn enum_samples(&self, enum_info: &EnumInfo) -> Result<Vec<(String, String)>, NoSample> {
+        if enum_info.variants.is_empty() {
+            return Err(NoSample::NoVariants(enum_info.name.clone()));
+        }
+        let type_path = format!("{}::{}", enum_info.module_path, enum_info.name);
+        let mut stack = vec![type_path];
+
+        enum_info
+            .variants
+            .iter()
+            .map(|variant| {
+                let value = self.variant_sample(variant, Fill::Full, &mut stack)?;
+                Ok((variant.name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// TypeScript literal of a value of `type_kind`. `stack` holds the types being built,
+    /// so self-references end in an empty collection.
+    fn sample(
+        &self,
+        type_kind: &TypeKind,
+        fill: Fill,
+        stack: &mut Vec<String>,
+    ) -> Result<String, NoSample> {
+        match type_kind {
+            TypeKind::Primitive(name) => Ok(primitive_sample(name, fill)),
+            TypeKind::String => Ok(match fill {
+                Fill::Full => "'Borsh ✓'".to_string(),
+                Fill::Empty => "''".to_string(),
+            }),
+            TypeKind::Struct(name, path) | TypeKind::Enum(name, path) => {
+                if type_override(self.config, name, path).is_some() {
+                    return Err(NoSample::Unknown(name.clone()));
+                }
+                if stack.contains(path) {
+                    return Err(NoSample::Recursive);
+                }
+
+                stack.push(path.clone());
+                let value = if let Some(struct_info) = self.structs.get(path) {
+                    let fields = struct_info
+                        .fields
+                        .iter()
+                        .map(|field| {
+                            Ok(format!(
+                                "{}: {}",
+                                self.config.naming.field_key(&field.name),
+                                self.sample(&field.type_kind, fill, stack)?
+                            ))
+                        })
+                        .collect::<Result<Vec<_>, _>>();
+                    fields.map(|fields| object(&fields))
+                } else if let Some(enum_info) = self.enums.get(path) {
+                    match enum_info.variants.first() {
+                        Some(variant) => self.variant_sample(variant, fill, stack),
+                        None => Err(NoSample::NoVariants(name.clone())),
+                    }
+                } else {
+                    Err(NoSample::Unknown(name.clone()))
+                };
+                stack.pop();
+
+                value
+            }
+            TypeKind::Vec(inner) => match fill {
+                Fill::Empty => Ok("[]".to_string()),
+                Fill::Full => match self.sample(inner, fill, stack) {
+                    Ok(value) => Ok(format!("[{}]", value)),
+                    Err(NoSample::Recursive) => Ok("[]".to_string()),
+                    Err(unknown) => Err(unknown),
+                },
+            },
+            TypeKind::Option(inner) => match fill {
+                Fill::Empty => Ok("null".to_string()),
+                Fill::Full => match self.sample(inner, fill, stack) {
+                    Err(NoSample::Recursive) => Ok("null".to_string()),
+                    value => value,
+                },
+            },
+            TypeKind::HashMap(key, value) => match fill {
+                Fill::Empty => Ok("new Map()".to_string()),
+                Fill::Full => {
+                    let key = self.sample(key, fill, stack)?;
+                    match self.sample(value, fill, stack) {
+                        Ok(value) => Ok(format!("new Map([[{}, {}]])", key, value)),
+                        Err(NoSample::Recursive) => Ok("new Map()".to_string()),
+                        Err(unknown) => Err(unknown),
+                    }
+                }
+            },
+            TypeKind::Array(inner, size) => {
+                if *size == 0 {
+                    return Ok("[]".to_string());
+                }
+                let value = self.sample(inner, fill, stack)?;
+                if *size > MAX_INLINE_ARRAY {
+                    Ok(format!(
+                        "Array.from({{ length: {} }}, () => {})",
+                        size, value
+                    ))
+                } else {
+                    Ok(format!("[{}]", vec![value; *size].join(", ")))
+                }
+            }
+        }
+    }
+
+    /// A decoded variant: a single-key object holding the variant's value
+    fn variant_sample(
+        &self,
+        variant: &EnumVariant,
+        fill: Fill,
+        stack: &mut Vec<String>,
+    ) -> Result<String, NoSample> {
+        let values = variant
+            .fields
+            .iter()
+            .flatten()
+            .map(|field| self.sample(&field.type_kind, fill, stack))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(variant_literal(variant, &values, self.config))
+    }
+}
+
+fn suite(type_name: &str, schema_name: &str, samples: &[(String, String)]) -> String {
+    let samples: Vec<_> = samples
+        .iter()
+        .map(|(label, value)| format!("        ['{}', {}],\n", label, value))
+        .collect();
+
+    let mut output = format!("describe('{}', () => {{\n", type_name);
+    output.push_str(&format!(
+        "    const samples: [string, {}][] = [\n{}    ];\n\n",
+        type_name,
+        samples.concat()
+    ));
+    output.push_str("    it.each(samples)('round-trips %s', (_, value) => {\n");
+    output.push_str(&format!(
+        "        expect({0}.deserialize({0}.serialize(value))).toEqual(value);\n",
+        schema_name
+    ));
+    output.push_str("    });\n});\n");
+    output
+}
+
+/// Import of the test functions from the configured framework
+pub(crate) fn framework_import(config: &Config) -> String {
+    let framework = match config.test_suites {
+        TestSuites::Jest => "@jest/globals",
+        TestSuites::Vitest | TestSuites::None => "vitest",
+    };
+    format!("import {{ describe, expect, it }} from '{}';\n", framework)
+}
+
+/// Why a sample of `name` isn't tested, when the type `unknown` it contains has no known encoding
+pub(crate) fn unknown_encoding(name: &str, unknown: &str) -> String {
+    format!(
+        "{} is not tested: the encoding of `{}` isn't known",
+        name, unknown
+    )
+}
+
+/// Literal of a decoded variant, a single-key object holding the variant's value, from
+/// the literals of its fields in order
+pub(crate) fn variant_literal(variant: &EnumVariant, values: &[String], config: &Config) -> String {
+    let key = config.naming.variant_key(&variant.name);
+    let value = match &variant.fields {
+        None => "{}".to_string(),
+        Some(fields) if is_tuple(fields) && values.len() == 1 => values[0].clone(),
+        Some(fields) if is_tuple(fields) && config.tuple_variants == TupleVariants::Tuple => {
+            format!("[{}]", values.join(", "))
+        }
+        Some(fields) => {
+            let entries: Vec<_> = fields
+                .iter()
+                .zip(values)
+                .enumerate()
+                .map(|(index, (field, value))| {
+                    if field.name.is_empty() {
+                        format!("_{}: {}", index, value)
+                    } else {
+                        format!("{}: {}", config.naming.field_key(&field.name), value)
+                    }
+                })
+                .collect();
+            object(&entries)
+        }
+    };
+    format!("{{ {}: {} }}", key, value)
+}
+
+/// Object literal of `key: value` entries
+pub(crate) fn object(entries: &[String]) -> String {
+    if entries.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", entries.join(", "))
+    }
+}
+
+/// The extreme value of each integer type, so truncation and sign errors show up
+fn primitive_sample(name: &str, fill: Fill) -> String {
+    let value = match (name, fill) {
+        ("bool", Fill::Full) => "true",
+        ("bool", Fill::Empty) => "false",
+        ("u64" | "i64" | "u128" | "i128", Fill::Empty) => "0n",
+        (_, Fill::Empty) => "0",
+        ("u8", _) => "255",
+        ("u16", _) => "65535",
+        ("u32", _) => "4294967295",
+        ("u64", _) => "18446744073709551615n",
+        ("u128", _) => "340282366920938463463374607431768211455n",
+        ("i8", _) => "-128",
+        ("i16", _) => "-32768",
+        ("i32", _) => "-2147483648",
+        ("i64", _) => "-9223372036854775808n",
+        ("i128", _) => "-170141183460469231731687303715884105728n",
+        // Exactly representable, so `toEqual` holds after a round trip through 32 bits
+        ("f32", _) => "1.5",
+        (_, _) => "0.1",
+    };
+    value.to_string()
+}
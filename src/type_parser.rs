@@ -1,13 +1,14 @@
 use anyhow::Result;
 use log::debug;
 use quote::ToTokens;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use syn::{
     visit::{self, Visit},
     Fields, File, GenericArgument, ItemEnum, ItemStruct, PathArguments, Type, TypePath,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TypeKind {
     Primitive(String),
     Struct(String, String), // (name, module_path)
@@ -19,30 +20,143 @@ pub enum TypeKind {
     String,
 }
 
-#[derive(Debug, Clone)]
+impl TypeKind {
+    /// The struct or enum at `type_path`, which is taken to be an enum unless it's in `structs`
+    pub(crate) fn named(type_path: &str, structs: &HashMap<String, StructInfo>) -> Self {
+        let name = type_path
+            .rsplit("::")
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        if structs.contains_key(type_path) {
+            TypeKind::Struct(name, type_path.to_string())
+        } else {
+            TypeKind::Enum(name, type_path.to_string())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StructInfo {
     pub name: String,
     pub module_path: String,
     pub fields: Vec<FieldInfo>,
+    #[serde(default, skip_serializing_if = "Docs::is_empty")]
+    pub docs: Docs,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FieldInfo {
     pub name: String,
     pub type_kind: TypeKind,
+    #[serde(default, skip_serializing_if = "Docs::is_empty")]
+    pub docs: Docs,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnumInfo {
     pub name: String,
     pub module_path: String,
     pub variants: Vec<EnumVariant>,
+    #[serde(default, skip_serializing_if = "Docs::is_empty")]
+    pub docs: Docs,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnumVariant {
     pub name: String,
     pub fields: Option<Vec<FieldInfo>>,
+    #[serde(default, skip_serializing_if = "Docs::is_empty")]
+    pub docs: Docs,
+}
+
+/// Documentation attached to an item in the Rust source
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Docs {
+    /// Text of the doc comments, one line per `///` line
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lines: Vec<String>,
+    /// Set by `#[deprecated]`, holding its note (empty without one)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+}
+
+impl Docs {
+    /// Reads `///` comments (`#[doc = "..."]`) and `#[deprecated]` from attributes
+    pub fn from_attrs(attrs: &[syn::Attribute]) -> Self {
+        let mut docs = Docs::default();
+
+        for attr in attrs {
+            if attr.path().is_ident("doc") {
+                if let syn::Meta::NameValue(syn::MetaNameValue {
+                    value:
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(text),
+                            ..
+                        }),
+                    ..
+                }) = &attr.meta
+                {
+                    // `/// text` keeps the space after the slashes
+                    for line in text.value().split('\n') {
+                        docs.lines.push(
+                            line.strip_prefix(' ')
+                                .unwrap_or(line)
+                                .trim_end()
+                                .to_string(),
+                        );
+                    }
+                }
+            } else if attr.path().is_ident("deprecated") {
+                docs.deprecated = Some(deprecation_note(&attr.meta).unwrap_or_default());
+            }
+        }
+
+        // Block comments start and end with blank lines
+        while docs
+            .lines
+            .first()
+            .is_some_and(|line| line.trim().is_empty())
+        {
+            docs.lines.remove(0);
+        }
+        while docs.lines.last().is_some_and(|line| line.trim().is_empty()) {
+            docs.lines.pop();
+        }
+
+        docs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty() && self.deprecated.is_none()
+    }
+}
+
+/// Note of `#[deprecated = "note"]` or `#[deprecated(note = "note")]`
+fn deprecation_note(meta: &syn::Meta) -> Option<String> {
+    match meta {
+        syn::Meta::NameValue(syn::MetaNameValue {
+            value:
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(note),
+                    ..
+                }),
+            ..
+        }) => Some(note.value()),
+        syn::Meta::List(list) => {
+            let mut note = None;
+            list.parse_nested_meta(|nested| {
+                let value: syn::LitStr = nested.value()?.parse()?;
+                if nested.path.is_ident("note") {
+                    note = Some(value.value());
+                }
+                Ok(())
+            })
+            .ok()?;
+            note
+        }
+        _ => None,
+    }
 }
 
 pub struct TypeParser {
@@ -92,6 +206,32 @@ impl TypeParser {
         }
     }
 
+    /// Resolves the module part of a type path: `self::` and `super::` are relative to
+    /// the current module, `crate::` to the input root, anything else is taken as is
+    fn resolve_module_path(&self, segments: &[String]) -> String {
+        let mut resolved: Vec<&str> = match segments.first().map(String::as_str) {
+            Some("self") | Some("super") => self
+                .module_path
+                .split("::")
+                .filter(|part| !part.is_empty())
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        for segment in segments {
+            match segment.as_str() {
+                "crate" => resolved.clear(),
+                "self" => {}
+                "super" => {
+                    resolved.pop();
+                }
+                segment => resolved.push(segment),
+            }
+        }
+
+        resolved.join("::")
+    }
+
     fn parse_type(&self, ty: &Type) -> TypeKind {
         debug!("Parsing type: {}", ty.to_token_stream());
 
@@ -114,8 +254,8 @@ impl TypeParser {
 
                     match type_name.as_str() {
                         // Primitive types
-                        "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64" | "f32"
-                        | "f64" => TypeKind::Primitive(type_name),
+                        "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64"
+                        | "i128" | "f32" | "f64" | "bool" => TypeKind::Primitive(type_name),
                         "String" => TypeKind::String,
                         "Vec" => {
                             if let PathArguments::AngleBracketed(args) = &segment.arguments {
@@ -157,17 +297,13 @@ impl TypeParser {
                         _ => {
                             // If path has multiple segments, it's a cross-module reference
                             let module_path = if path.segments.len() > 1 {
-                                let mut segments: Vec<_> = path
+                                let segments: Vec<_> = path
                                     .segments
                                     .iter()
                                     .take(path.segments.len() - 1)
                                     .map(|s| s.ident.to_string())
                                     .collect();
-                                // Handle super:: by using the module directly
-                                if segments[0] == "super" {
-                                    segments.remove(0);
-                                }
-                                segments.join("::")
+                                self.resolve_module_path(&segments)
                             } else {
                                 self.module_path.clone()
                             };
@@ -228,6 +364,7 @@ impl<'ast> Visit<'ast> for TypeParser {
                     fields.push(FieldInfo {
                         name: ident.to_string(),
                         type_kind: self.parse_type(&field.ty),
+                        docs: Docs::from_attrs(&field.attrs),
                     });
                 }
             }
@@ -239,6 +376,7 @@ impl<'ast> Visit<'ast> for TypeParser {
                 name: struct_name,
                 module_path: self.module_path.clone(),
                 fields,
+                docs: Docs::from_attrs(&node.attrs),
             },
         );
 
@@ -265,6 +403,7 @@ impl<'ast> Visit<'ast> for TypeParser {
                         .map(|field| FieldInfo {
                             name: field.ident.as_ref().unwrap().to_string(),
                             type_kind: self.parse_type(&field.ty),
+                            docs: Docs::from_attrs(&field.attrs),
                         })
                         .collect(),
                 ),
@@ -275,6 +414,7 @@ impl<'ast> Visit<'ast> for TypeParser {
                         .map(|field| FieldInfo {
                             name: String::new(),
                             type_kind: self.parse_type(&field.ty),
+                            docs: Docs::from_attrs(&field.attrs),
                         })
                         .collect(),
                 ),
@@ -284,6 +424,7 @@ impl<'ast> Visit<'ast> for TypeParser {
             variants.push(EnumVariant {
                 name: variant_name,
                 fields,
+                docs: Docs::from_attrs(&variant.attrs),
             });
         }
 
@@ -293,6 +434,7 @@ impl<'ast> Visit<'ast> for TypeParser {
                 name: enum_name,
                 module_path: self.module_path.clone(),
                 variants,
+                docs: Docs::from_attrs(&node.attrs),
             },
         );
 
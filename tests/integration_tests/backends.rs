@@ -0,0 +1,378 @@
+use super::*;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use zorsh_gen_rs::dependency_resolver::TypeDependencies;
+use zorsh_gen_rs::{Backend, BackendKind, BorshSchemaContainer, SourceFile};
+
+const MODELS: &str = r#"
+    #[derive(BorshSerialize)]
+    pub struct Player {
+        name: String,
+        item: super::items::Item,
+    }
+"#;
+
+const ITEMS: &str = r#"
+    #[derive(BorshSerialize)]
+    pub struct Item {
+        id: u32,
+    }
+"#;
+
+/// Lists the types of each module, to check the trait is enough for a new target
+struct TypeListBackend;
+
+impl Backend for TypeListBackend {
+    fn generate_module(&self, module: &str, dependencies: &TypeDependencies) -> Result<String> {
+        let mut output = String::new();
+        for (from_module, type_names) in &dependencies.module_imports {
+            if from_module != module {
+                let names: Vec<_> = type_names.iter().cloned().collect();
+                output.push_str(&self.render_import(module, from_module, &names));
+            }
+        }
+        for type_path in &dependencies.ordered_types {
+            if type_path.rsplit_once("::").map(|(m, _)| m) == Some(module) {
+                output.push_str(&format!("type {}\n", type_path));
+            }
+        }
+        Ok(output)
+    }
+
+    fn module_file(&self, module: &str) -> PathBuf {
+        PathBuf::from(format!("{}.txt", module.replace("::", "-")))
+    }
+
+    fn render_import(&self, _module: &str, from_module: &str, type_names: &[String]) -> String {
+        format!("use {} from {}\n", type_names.join(","), from_module)
+    }
+}
+
+#[test]
+fn test_custom_backend() -> Result<()> {
+    let generator = ZorshGen::new(Config::default());
+    let ir = generator.parse(&[
+        SourceFile::in_memory("models", MODELS),
+        SourceFile::in_memory("items", ITEMS),
+    ])?;
+
+    let generated = generator.generate_ir_with(&ir, &TypeListBackend)?;
+
+    assert_eq!(
+        generated.files[&PathBuf::from("models.txt")],
+        "use Item from items\ntype models::Player\n"
+    );
+    assert_eq!(
+        generated.files[&PathBuf::from("items.txt")],
+        "type items::Item\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_default_backend_is_zorsh() -> Result<()> {
+    let generated = ZorshGen::new(Config::default()).generate(&[
+        SourceFile::in_memory("models", MODELS),
+        SourceFile::in_memory("items", ITEMS),
+    ])?;
+
+    let models = &generated.files[&PathBuf::from("models.ts")];
+    assert!(models.contains("import { ItemSchema } from './items';"));
+    assert!(models.contains("export const PlayerSchema = b.struct({"));
+
+    Ok(())
+}
+
+#[test]
+fn test_borsh_js_backend() -> Result<()> {
+    let input = r#"
+        #[derive(BorshSerialize)]
+        struct Item {
+            name: String,
+            tags: [u8; 4],
+        }
+
+        #[derive(BorshSerialize)]
+        struct Player {
+            name: String,
+            score: u32,
+            inventory: HashMap<String, Vec<Item>>,
+            status: Option<Status>,
+        }
+
+        #[derive(BorshSerialize)]
+        enum Status {
+            Idle,
+            Moving(u64),
+            Fighting { enemy: String, damage: u16 },
+        }
+    "#;
+
+    let config = Config {
+        backend: BackendKind::BorshJs,
+        ..Config::default()
+    };
+    let output = ZorshGen::new(config).convert_str(input)?;
+    insta::assert_snapshot!(output);
+    Ok(())
+}
+
+#[test]
+fn test_borsh_js_backend_imports() -> Result<()> {
+    let config = Config {
+        backend: BackendKind::BorshJs,
+        ..Config::default()
+    };
+    let generated = ZorshGen::new(config).generate(&[
+        SourceFile::in_memory("models", MODELS),
+        SourceFile::in_memory("items", ITEMS),
+    ])?;
+
+    let models = &generated.files[&PathBuf::from("models.ts")];
+    assert!(models.starts_with("import type { Schema } from 'borsh';\n"));
+    assert!(models.contains("import { ItemSchema } from './items';"));
+    assert!(models.contains("item: ItemSchema"));
+
+    Ok(())
+}
+
+#[test]
+fn test_python_backend() -> Result<()> {
+    let input = r#"
+        #[derive(BorshSerialize)]
+        struct Item {
+            name: String,
+            tags: [u8; 4],
+            weight: f32,
+        }
+
+        #[derive(BorshSerialize)]
+        struct Player {
+            name: String,
+            score: u32,
+            inventory: HashMap<String, Vec<Item>>,
+            status: Option<Status>,
+        }
+
+        #[derive(BorshSerialize)]
+        enum Status {
+            Idle,
+            Moving(u64),
+            Fighting { enemy: String, from: u16 },
+        }
+    "#;
+
+    let config = Config {
+        backend: BackendKind::Python,
+        ..Config::default()
+    };
+    let output = ZorshGen::new(config).convert_str(input)?;
+    insta::assert_snapshot!(output);
+    Ok(())
+}
+
+#[test]
+fn test_python_backend_packages() -> Result<()> {
+    let config = Config {
+        backend: BackendKind::Python,
+        ..Config::default()
+    };
+    let generated = ZorshGen::new(config).generate(&[
+        SourceFile::in_memory(
+            "game",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct World {
+                    player: models::player::Player,
+                }
+            "#,
+        ),
+        SourceFile::in_memory(
+            "game::models::player",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Player {
+                    item: game::items::Item,
+                }
+            "#,
+        ),
+        SourceFile::in_memory(
+            "game::items",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Item {
+                    id: u32,
+                }
+            "#,
+        ),
+    ])?;
+
+    let paths: Vec<_> = generated.files.keys().cloned().collect();
+    assert_eq!(
+        paths,
+        vec![
+            PathBuf::from("__init__.py"),
+            PathBuf::from("game/__init__.py"),
+            PathBuf::from("game/items.py"),
+            PathBuf::from("game/models/__init__.py"),
+            PathBuf::from("game/models/player.py"),
+        ]
+    );
+
+    let player = &generated.files[&PathBuf::from("game/models/player.py")];
+    assert!(player.contains("from ..items import Item, ItemSchema\n"));
+    assert!(player.contains("    \"item\" / ItemSchema,\n"));
+
+    // `game` has submodules, so its own types live in the package's __init__.py
+    let game = &generated.files[&PathBuf::from("game/__init__.py")];
+    assert!(game.contains("WorldSchema = CStruct("));
+
+    Ok(())
+}
+
+#[test]
+fn test_json_schema_backend() -> Result<()> {
+    let input = r#"
+        #[derive(BorshSerialize)]
+        struct Item {
+            id: u64,
+            tags: [u8; 4],
+            weights: HashMap<String, f32>,
+        }
+
+        #[derive(BorshSerialize)]
+        struct Player {
+            balance: u128,
+            active: bool,
+            items: Vec<Item>,
+            positions: HashMap<u16, i32>,
+            status: Option<Status>,
+        }
+
+        #[derive(BorshSerialize)]
+        enum Status {
+            Idle,
+            Moving(i64),
+            Teleporting(u8, u8),
+            Fighting { enemy: String },
+        }
+    "#;
+
+    let config = Config {
+        backend: BackendKind::JsonSchema,
+        ..Config::default()
+    };
+    let output = ZorshGen::new(config).convert_str(input)?;
+    insta::assert_snapshot!(output);
+    Ok(())
+}
+
+#[test]
+fn test_json_schema_backend_refs() -> Result<()> {
+    let sources = [
+        SourceFile::in_memory("models", MODELS),
+        SourceFile::in_memory("items", ITEMS),
+    ];
+
+    let config = Config {
+        backend: BackendKind::JsonSchema,
+        ..Config::default()
+    };
+    let generated = ZorshGen::new(config).generate(&sources)?;
+    let models = &generated.files[&PathBuf::from("models.schema.json")];
+    assert!(models.contains(r##""$ref": "./items.schema.json#/$defs/Item""##));
+
+    let config = Config {
+        backend: BackendKind::JsonSchema,
+        bundle: true,
+        ..Config::default()
+    };
+    let generated = ZorshGen::new(config).generate(&sources)?;
+    let paths: Vec<_> = generated.files.keys().cloned().collect();
+    assert_eq!(paths, vec![PathBuf::from("schema.json")]);
+    let bundle = &generated.files[&PathBuf::from("schema.json")];
+    assert!(bundle.contains(r#""items::Item": {"#));
+    assert!(bundle.contains(r##""$ref": "#/$defs/items::Item""##));
+
+    Ok(())
+}
+
+#[test]
+fn test_borsh_schema_backend() -> Result<()> {
+    let input = r#"
+        #[derive(BorshSerialize)]
+        struct Item {
+            id: u64,
+            tags: [u8; 4],
+        }
+
+        #[derive(BorshSerialize)]
+        struct Player {
+            name: String,
+            items: Vec<Item>,
+            scores: HashMap<String, u32>,
+            status: Option<Status>,
+        }
+
+        #[derive(BorshSerialize)]
+        enum Status {
+            Idle,
+            Moving(i64),
+            Fighting { enemy: String },
+        }
+    "#;
+
+    let config = Config {
+        backend: BackendKind::BorshSchema,
+        ..Config::default()
+    };
+    let output = ZorshGen::new(config).convert_str(input)?;
+    insta::assert_snapshot!(output);
+    Ok(())
+}
+
+#[test]
+fn test_borsh_schema_backend_containers() -> Result<()> {
+    let sources = [
+        SourceFile::in_memory("models", MODELS),
+        SourceFile::in_memory("items", ITEMS),
+    ];
+    let config = Config {
+        backend: BackendKind::BorshSchema,
+        ..Config::default()
+    };
+    let generated = ZorshGen::new(config).generate(&sources)?;
+
+    // Containers carry every definition they reach, so nothing is imported across modules
+    let models: BTreeMap<String, BorshSchemaContainer> =
+        serde_json::from_str(&generated.files[&PathBuf::from("models.borsh-schema.json")])?;
+    let player = &models["Player"];
+    assert_eq!(player.declaration, "Player");
+    assert_eq!(
+        player.definitions.keys().collect::<Vec<_>>(),
+        vec!["Item", "Player", "String", "u32", "u8"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_python_backend_enum_without_variants() -> Result<()> {
+    let config = Config {
+        backend: BackendKind::Python,
+        ..Config::default()
+    };
+    let output = ZorshGen::new(config).convert_str(
+        r#"
+            #[derive(BorshSerialize)]
+            enum Never {}
+        "#,
+    )?;
+
+    assert!(output.contains("from typing import NoReturn\n"));
+    assert!(output.contains("NeverSchema = Enum(enum_name=\"Never\")\n"));
+    assert!(output.contains("Never = NoReturn\n"));
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use super::*;
+use anyhow::Result;
+use std::path::PathBuf;
+use zorsh_gen_rs::SourceFile;
+
+fn struct_source(name: &str) -> String {
+    format!(
+        r#"
+            #[derive(BorshSerialize)]
+            pub struct {} {{
+                id: u32,
+            }}
+        "#,
+        name
+    )
+}
+
+#[test]
+fn test_barrels() -> Result<()> {
+    let sources = [
+        SourceFile::in_memory("models::player", &struct_source("Player")),
+        SourceFile::in_memory("models::items", &struct_source("Item")),
+        SourceFile::in_memory("models::items::weapon", &struct_source("Weapon")),
+        SourceFile::in_memory("shop", &struct_source("Item")),
+    ];
+
+    let config = Config {
+        barrels: true,
+        ..Config::default()
+    };
+    let generated = ZorshGen::new(config).generate(&sources)?;
+
+    let barrel = |path: &str| generated.files[&PathBuf::from(path)].as_str();
+    assert_eq!(
+        barrel("models/items/index.ts"),
+        "export * from './weapon';\n"
+    );
+    // `items.ts` and `items/index.ts` both exist, so directories are imported by their index
+    assert_eq!(
+        barrel("models/index.ts"),
+        "export * from './items';\nexport * from './items/index';\nexport * from './player';\n"
+    );
+    // Both export `Item`, so neither can be flattened into the root barrel
+    assert_eq!(
+        barrel("index.ts"),
+        "export * as models from './models/index';\nexport * as shop from './shop';\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_barrels_only_count_generated_exports() -> Result<()> {
+    let sources = [
+        SourceFile::in_memory(
+            "status",
+            r#"
+                #[derive(BorshSerialize)]
+                pub enum Status {
+                    Active,
+                    Inactive,
+                }
+            "#,
+        ),
+        SourceFile::in_memory("values", &struct_source("StatusValues")),
+    ];
+
+    let config = Config {
+        barrels: true,
+        ..Config::default()
+    };
+    let generated = ZorshGen::new(config).generate(&sources)?;
+
+    // Without `enum_values` there is no `StatusValues` in `status.ts` to collide with
+    assert_eq!(
+        generated.files[&PathBuf::from("index.ts")],
+        "export * from './status';\nexport * from './values';\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_barrels_are_optional() -> Result<()> {
+    let sources = [SourceFile::in_memory("models", &struct_source("Player"))];
+
+    let generated = ZorshGen::new(Config::default()).generate(&sources)?;
+    let paths: Vec<_> = generated.files.keys().cloned().collect();
+    assert_eq!(paths, vec![PathBuf::from("models.ts")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_barrel_namespaces_are_unique() -> Result<()> {
+    let sources = [
+        SourceFile::in_memory("models::items", &struct_source("Item")),
+        SourceFile::in_memory("models::items::weapon", &struct_source("Item")),
+    ];
+
+    let config = Config {
+        barrels: true,
+        ..Config::default()
+    };
+    let generated = ZorshGen::new(config).generate(&sources)?;
+
+    // `items.ts` and `items/index.ts` both export `Item`, so both need a namespace
+    assert_eq!(
+        generated.files[&PathBuf::from("models/index.ts")],
+        "export * as items from './items';\nexport * as items_2 from './items/index';\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_barrels_reject_index_modules() {
+    let sources = [SourceFile::in_memory(
+        "models::index",
+        &struct_source("Player"),
+    )];
+
+    let config = Config {
+        barrels: true,
+        ..Config::default()
+    };
+    let error = ZorshGen::new(config).generate(&sources).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "The module file models/index.ts would be overwritten by its directory's barrel, rename the module or disable barrels"
+    );
+}
@@ -1,4 +1,7 @@
+use super::*;
 use anyhow::Result;
+use std::path::PathBuf;
+use zorsh_gen_rs::SourceFile;
 
 #[test]
 fn test_primitive_types() -> Result<()> {
@@ -23,6 +26,25 @@ fn test_primitive_types() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_wide_integers_and_bool() -> Result<()> {
+    let input = r#"
+        #[derive(BorshSerialize)]
+        struct Account {
+            balance: u128,
+            debt: i128,
+            active: bool,
+            flags: Vec<bool>,
+            limit: Option<u128>,
+        }
+    "#;
+
+    let output = zorsh_gen_rs::convert_str(input)?;
+    assert!(!output.contains("boolSchema"));
+    insta::assert_snapshot!(output);
+    Ok(())
+}
+
 #[test]
 fn test_string_types() -> Result<()> {
     let input = r#"
@@ -68,3 +90,37 @@ fn test_basic_enums() -> Result<()> {
     insta::assert_snapshot!(output);
     Ok(())
 }
+
+#[test]
+fn test_only_unit_enums_have_value_lists() -> Result<()> {
+    let input = r#"
+        #[derive(BorshSerialize)]
+        enum Direction {
+            Up,
+            Down,
+        }
+
+        #[derive(BorshSerialize)]
+        enum Command {
+            Stop,
+            Move(Direction),
+        }
+    "#;
+
+    let generated = ZorshGen::new(Config {
+        enum_values: true,
+        ..Config::default()
+    })
+    .generate(&[SourceFile::in_memory("game", input)])?;
+    let output = &generated.files[&PathBuf::from("game.ts")];
+    assert!(output.contains("export const DirectionValues = ['Up', 'Down'] as const;\n"));
+    assert!(output.contains(
+        "export const DirectionDiscriminants = {\n    Up: 0,\n    Down: 1,\n} as const;\n"
+    ));
+    assert!(!output.contains("CommandValues"));
+
+    let output = zorsh_gen_rs::convert_str(input)?;
+    assert!(!output.contains("Values"));
+    assert!(!output.contains("Discriminants"));
+    Ok(())
+}
@@ -0,0 +1,320 @@
+use super::*;
+use anyhow::Result;
+use serde_json::json;
+use zorsh_gen_rs::borsh_codec::{from_hex, to_hex};
+use zorsh_gen_rs::{BorshCodec, SourceFile};
+
+fn codec() -> Result<BorshCodec> {
+    let ir = ZorshGen::new(Config::default()).parse(&[
+        SourceFile::in_memory(
+            "models",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Player {
+                    name: String,
+                    level: u8,
+                    gold: u64,
+                    position: [i16; 2],
+                    status: Status,
+                    guild: Option<String>,
+                    inventory: HashMap<String, u32>,
+                    scores: HashMap<u32, i128>,
+                }
+
+                #[derive(BorshSerialize)]
+                pub enum Status {
+                    Idle,
+                    Moving(i32, i32),
+                    Trading(u32),
+                    Fighting { enemy: String, damage: f32 },
+                }
+            "#,
+        ),
+        SourceFile::in_memory(
+            "other",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Status {
+                    active: bool,
+                }
+            "#,
+        ),
+    ])?;
+    Ok(BorshCodec::from_ir(&ir))
+}
+
+#[test]
+fn test_known_encoding() -> Result<()> {
+    let codec = codec()?;
+    let value = json!({
+        "name": "Ab",
+        "level": 7,
+        "gold": "258",
+        "position": [-1, 2],
+        "status": { "Moving": [1, -2] },
+        "guild": null,
+        "inventory": { "gem": 3 },
+        "scores": [[5, "-1"]],
+    });
+
+    let bytes = codec.encode("models::Player", &value)?;
+    assert_eq!(
+        to_hex(&bytes),
+        [
+            "020000004162",                     // name
+            "07",                               // level
+            "0201000000000000",                 // gold
+            "ffff0200",                         // position
+            "0101000000feffffff",               // status
+            "00",                               // guild
+            "010000000300000067656d03000000",   // inventory
+            "0100000005000000",                 // scores: length and key
+            "ffffffffffffffffffffffffffffffff", // scores: value
+        ]
+        .concat()
+    );
+    assert_eq!(codec.decode("models::Player", &bytes)?, value);
+
+    Ok(())
+}
+
+#[test]
+fn test_enum_variants_round_trip() -> Result<()> {
+    let codec = codec()?;
+    for value in [
+        json!({ "Idle": {} }),
+        json!({ "Moving": [-5, 5] }),
+        json!({ "Trading": 4294967295u32 }),
+        json!({ "Fighting": { "enemy": "Orc", "damage": 1.5 } }),
+    ] {
+        let bytes = codec.encode("models::Status", &value)?;
+        assert_eq!(codec.decode("models::Status", &bytes)?, value);
+    }
+
+    // Unit variants can be written as a plain string
+    assert_eq!(codec.encode("models::Status", &json!("Idle"))?, vec![0]);
+
+    Ok(())
+}
+
+#[test]
+fn test_integers_beyond_32_bits_are_strings() -> Result<()> {
+    let codec = codec()?;
+    let bytes = codec.encode("models::Status", &json!({ "Trading": 1 }))?;
+    assert_eq!(bytes, vec![2, 1, 0, 0, 0]);
+
+    let value = json!({
+        "name": "",
+        "level": 0,
+        "gold": 18446744073709551615u64,
+        "position": [0, 0],
+        "status": "Idle",
+        "guild": "G",
+        "inventory": {},
+        "scores": [],
+    });
+    let decoded = codec.decode("models::Player", &codec.encode("models::Player", &value)?)?;
+    assert_eq!(decoded["gold"], json!("18446744073709551615"));
+    assert_eq!(decoded["guild"], json!("G"));
+    assert_eq!(decoded["status"], json!({ "Idle": {} }));
+
+    Ok(())
+}
+
+#[test]
+fn test_map_entries_are_sorted_by_key() -> Result<()> {
+    let codec = codec()?;
+    let value = |inventory| {
+        json!({
+            "name": "",
+            "level": 0,
+            "gold": "0",
+            "position": [0, 0],
+            "status": "Idle",
+            "guild": null,
+            "inventory": inventory,
+            "scores": [[10, 1], [9, 2]],
+        })
+    };
+
+    assert_eq!(
+        codec.encode("models::Player", &value(json!({ "b": 1, "a": 2 })))?,
+        codec.encode("models::Player", &value(json!({ "a": 2, "b": 1 })))?
+    );
+    let decoded = codec.decode(
+        "models::Player",
+        &codec.encode("models::Player", &value(json!({})))?,
+    )?;
+    assert_eq!(decoded["scores"], json!([[9, "2"], [10, "1"]]));
+
+    Ok(())
+}
+
+#[test]
+fn test_map_keys_are_sorted_by_their_rust_type() -> Result<()> {
+    let ir = ZorshGen::new(Config::default()).parse(&[SourceFile::in_memory(
+        "keys",
+        r#"
+            #[derive(BorshSerialize)]
+            pub struct Point {
+                x: u8,
+                y: i8,
+            }
+
+            #[derive(BorshSerialize)]
+            pub struct Maps {
+                names: HashMap<String, u8>,
+                wide: HashMap<u128, u8>,
+                points: HashMap<Point, u8>,
+            }
+        "#,
+    )])?;
+    let codec = BorshCodec::from_ir(&ir);
+    let value = json!({
+        "names": { "9": 1, "10": 2 },
+        "wide": [["170141183460469231731687303715884105728", 1], ["1", 2]],
+        "points": [[{ "x": 1, "y": -1 }, 1], [{ "x": 0, "y": 5 }, 2], [{ "x": 1, "y": -2 }, 3]],
+    });
+
+    let decoded = codec.decode("keys::Maps", &codec.encode("keys::Maps", &value)?)?;
+    // Strings are compared bytewise, even when they look like numbers
+    let names: Vec<_> = decoded["names"].as_object().unwrap().keys().collect();
+    assert_eq!(names, vec!["10", "9"]);
+    // Above `i128::MAX`, but still ordered numerically
+    assert_eq!(
+        decoded["wide"],
+        json!([["1", 2], ["170141183460469231731687303715884105728", 1]])
+    );
+    assert_eq!(
+        decoded["points"],
+        json!([[{ "x": 0, "y": 5 }, 2], [{ "x": 1, "y": -2 }, 3], [{ "x": 1, "y": -1 }, 1]])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_lengths_are_checked() -> Result<()> {
+    let ir = ZorshGen::new(Config::default()).parse(&[SourceFile::in_memory(
+        "lengths",
+        r#"
+            #[derive(BorshSerialize)]
+            pub struct Empty {}
+
+            #[derive(BorshSerialize)]
+            pub struct Zero {
+                values: Vec<Empty>,
+            }
+
+            #[derive(BorshSerialize)]
+            pub struct Numbers {
+                values: Vec<u64>,
+            }
+        "#,
+    )])?;
+    let codec = BorshCodec::from_ir(&ir);
+
+    // Nothing bounds the number of zero-sized elements, so borsh-rs rejects them
+    let error = codec
+        .decode("lengths::Zero", &from_hex("ffffffff")?)
+        .unwrap_err();
+    assert!(format!("{:#}", error).contains("Collections of zero-sized types are not supported"));
+    assert_eq!(
+        codec.decode("lengths::Zero", &from_hex("00000000")?)?,
+        json!({ "values": [] })
+    );
+
+    let error = codec
+        .decode("lengths::Numbers", &from_hex("ffffffff 0100000000000000")?)
+        .unwrap_err();
+    assert!(format!("{:#}", error)
+        .contains("Length 4294967295 needs at least 34359738360 bytes, found 8 at offset 4"));
+
+    Ok(())
+}
+
+#[test]
+fn test_type_lookup() -> Result<()> {
+    let codec = codec()?;
+    assert_eq!(codec.resolve_type("Player")?, "models::Player");
+    assert_eq!(codec.resolve_type("other::Status")?, "other::Status");
+
+    let error = codec.resolve_type("Status").unwrap_err().to_string();
+    assert_eq!(
+        error,
+        "`Status` is ambiguous, use one of: models::Status, other::Status"
+    );
+    assert!(codec.resolve_type("Missing").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_input_is_rejected() -> Result<()> {
+    let codec = codec()?;
+
+    let error = codec.decode("other::Status", &[1, 0]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "1 trailing bytes after the value, at offset 1"
+    );
+
+    let error = codec.decode("models::Status", &[9]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid discriminant 9 for `models::Status`"
+    );
+
+    let error = codec.decode("models::Status", &[2, 1, 0]).unwrap_err();
+    assert!(format!("{:#}", error).contains("Unexpected end of input"));
+
+    let error = codec
+        .encode("models::Status", &json!({ "Fighting": { "enemy": "Orc" } }))
+        .unwrap_err();
+    assert!(format!("{:#}", error).contains("Missing field `damage`"));
+
+    let error = codec
+        .encode("models::Status", &json!({ "Trading": 4294967296u64 }))
+        .unwrap_err();
+    assert!(format!("{:#}", error).contains("in `Trading`"));
+
+    let player = json!({
+        "name": "",
+        "level": 0,
+        "gold": "0",
+        "position": [0, 0],
+        "status": "Idle",
+        "guild": null,
+        "inventory": {},
+        "scores": [[1, 1], ["1", 2]],
+    });
+    let error = codec.encode("models::Player", &player).unwrap_err();
+    assert!(format!("{:#}", error).contains("Duplicate key \"1\" in map"));
+
+    Ok(())
+}
+
+#[test]
+fn test_hex() -> Result<()> {
+    assert_eq!(from_hex("0x00ff 10")?, vec![0, 255, 16]);
+    assert_eq!(to_hex(&[0, 255, 16]), "00ff10");
+    assert!(from_hex("abc").is_err());
+    assert!(from_hex("é0").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_infinite_floats_round_trip() -> Result<()> {
+    let codec = codec()?;
+
+    for damage in [json!("Infinity"), json!("-Infinity"), json!(1.5)] {
+        let value = json!({ "Fighting": { "enemy": "Orc", "damage": damage } });
+        let bytes = codec.encode("models::Status", &value)?;
+        assert_eq!(codec.decode("models::Status", &bytes)?, value);
+    }
+
+    let nan = [3, 0, 0, 0, 0, 0, 0, 0xc0, 0x7f];
+    let error = codec.decode("models::Status", &nan).unwrap_err();
+    assert!(format!("{:#}", error).contains("Invalid float NaN"));
+    Ok(())
+}
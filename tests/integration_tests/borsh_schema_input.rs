@@ -0,0 +1,211 @@
+use super::*;
+use anyhow::Result;
+use std::path::PathBuf;
+use zorsh_gen_rs::{BackendKind, BorshSchemaLoader, SourceFile};
+
+/// Container as dumped by `BorshSchemaContainer::for_type::<Token>()`
+const TOKEN_CONTAINER: &str = r#"{
+  "declaration": "Token",
+  "definitions": {
+    "()": { "Primitive": 0 },
+    "AccountId": { "Struct": { "fields": { "UnnamedFields": ["String"] } } },
+    "HashMap<String, u64>": {
+      "Sequence": {
+        "length_width": 4,
+        "length_range": { "start": 0, "end": 4294967295 },
+        "elements": "(String, u64)"
+      }
+    },
+    "(String, u64)": { "Tuple": { "elements": ["String", "u64"] } },
+    "Option<AccountId>": {
+      "Enum": {
+        "tag_width": 1,
+        "variants": [[0, "None", "()"], [1, "Some", "AccountId"]]
+      }
+    },
+    "String": {
+      "Sequence": {
+        "length_width": 4,
+        "length_range": { "start": 0, "end": 4294967295 },
+        "elements": "u8"
+      }
+    },
+    "Token": {
+      "Struct": {
+        "fields": {
+          "NamedFields": [
+            ["owner", "Option<AccountId>"],
+            ["balances", "HashMap<String, u64>"],
+            ["hash", "[u8; 32]"],
+            ["kind", "TokenKind"]
+          ]
+        }
+      }
+    },
+    "TokenKind": {
+      "Enum": {
+        "tag_width": 1,
+        "variants": [
+          [0, "Fungible", "TokenKindFungible"],
+          [1, "Collectible", "TokenKindCollectible"]
+        ]
+      }
+    },
+    "TokenKindFungible": { "Struct": { "fields": { "NamedFields": [["decimals", "u8"]] } } },
+    "TokenKindCollectible": { "Struct": { "fields": "Empty" } },
+    "[u8; 32]": {
+      "Sequence": {
+        "length_width": 0,
+        "length_range": { "start": 32, "end": 32 },
+        "elements": "u8"
+      }
+    },
+    "u8": { "Primitive": 1 },
+    "u64": { "Primitive": 8 }
+  }
+}"#;
+
+#[test]
+fn test_borsh_schema_mixed_with_rust_sources() -> Result<()> {
+    let temp_dir = setup_test_dir();
+    let input_dir = setup_test_files(&temp_dir, &[("token.json", TOKEN_CONTAINER)]);
+
+    let config = Config {
+        borsh_schemas: [("external".to_string(), input_dir.join("token.json"))].into(),
+        ..Config::default()
+    };
+    let generated = ZorshGen::new(config).generate(&[SourceFile::in_memory(
+        "wallet",
+        r#"
+            #[derive(BorshSerialize)]
+            pub struct Wallet {
+                tokens: Vec<external::Token>,
+            }
+        "#,
+    )])?;
+
+    assert!(generated.diagnostics.is_empty());
+    let paths: Vec<_> = generated.files.keys().cloned().collect();
+    assert_eq!(
+        paths,
+        vec![PathBuf::from("external.ts"), PathBuf::from("wallet.ts")]
+    );
+    insta::assert_snapshot!(generated.files[&PathBuf::from("external.ts")]);
+    assert!(generated.files[&PathBuf::from("wallet.ts")].contains("tokens: b.vec(TokenSchema)"));
+
+    Ok(())
+}
+
+#[test]
+fn test_borsh_schema_round_trip() -> Result<()> {
+    let input = r#"
+        #[derive(BorshSerialize)]
+        struct Item {
+            id: u64,
+            tags: [u8; 4],
+        }
+
+        #[derive(BorshSerialize)]
+        enum Status {
+            Idle,
+            Moving(i64),
+            Teleporting(u8, u8),
+            Fighting { enemy: String },
+        }
+
+        #[derive(BorshSerialize)]
+        struct Player {
+            name: String,
+            items: Vec<Item>,
+            scores: HashMap<String, u32>,
+            status: Option<Status>,
+        }
+    "#;
+
+    let zorsh = ZorshGen::new(Config::default()).convert_str(input)?;
+    let containers = ZorshGen::new(Config {
+        backend: BackendKind::BorshSchema,
+        ..Config::default()
+    })
+    .convert_str(input)?;
+
+    // Loading the generated containers back gives the same types as parsing the source
+    let mut loader = BorshSchemaLoader::new("root".to_string());
+    loader.load_json(&containers)?;
+    let ir = zorsh_gen_rs::TypeIr::new(loader.structs, loader.enums)?;
+    let generated = ZorshGen::new(Config::default()).generate_ir(&ir)?;
+
+    assert_eq!(generated.files[&PathBuf::from("root.ts")], zorsh);
+    Ok(())
+}
+
+#[test]
+fn test_borsh_schema_rejects_tuples() {
+    let mut loader = BorshSchemaLoader::new("external".to_string());
+    let error = loader
+        .load_json(
+            r#"{
+                "declaration": "Pair",
+                "definitions": {
+                    "Pair": { "Struct": { "fields": { "NamedFields": [["value", "(u8, u8)"]] } } },
+                    "(u8, u8)": { "Tuple": { "elements": ["u8", "u8"] } },
+                    "u8": { "Primitive": 1 }
+                }
+            }"#,
+        )
+        .unwrap_err();
+
+    assert_eq!(error.to_string(), "Tuples are not supported: `(u8, u8)`");
+}
+
+#[test]
+fn test_borsh_schema_rejects_colliding_type_names() {
+    let mut loader = BorshSchemaLoader::new("external".to_string());
+    let error = loader
+        .load_json(
+            r#"{
+                "declaration": "Pair",
+                "definitions": {
+                    "Pair": { "Struct": { "fields": { "NamedFields": [["a", "a::Foo"], ["b", "b::Foo"]] } } },
+                    "a::Foo": { "Struct": { "fields": { "NamedFields": [["value", "u8"]] } } },
+                    "b::Foo": { "Struct": { "fields": { "NamedFields": [["value", "u64"]] } } },
+                    "u8": { "Primitive": 1 },
+                    "u64": { "Primitive": 8 }
+                }
+            }"#,
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "`a::Foo` and `b::Foo` would both generate `external::Foo`"
+    );
+}
+
+#[test]
+fn test_borsh_schema_rejects_other_length_widths() {
+    let mut loader = BorshSchemaLoader::new("external".to_string());
+    let error = loader
+        .load_json(
+            r#"{
+                "declaration": "Bytes",
+                "definitions": {
+                    "Bytes": { "Struct": { "fields": { "NamedFields": [["data", "SmallVec"]] } } },
+                    "SmallVec": {
+                        "Sequence": {
+                            "length_width": 1,
+                            "length_range": { "start": 0, "end": 255 },
+                            "elements": "u8"
+                        }
+                    },
+                    "u8": { "Primitive": 1 }
+                }
+            }"#,
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Unsupported length width 1 in `SmallVec`"
+    );
+}
@@ -0,0 +1,124 @@
+use super::*;
+use anyhow::Result;
+use zorsh_gen_rs::{ConfigFile, OutputStructure};
+
+#[test]
+fn test_discover_zorsh_toml_from_subdirectory() -> Result<()> {
+    let temp_dir = setup_test_dir();
+
+    let files = vec![
+        (
+            "zorsh.toml",
+            r#"
+                input = "src"
+                output = "generated"
+                only_annotated = false
+                output_structure = "flat"
+                ignored_patterns = ["generated/"]
+
+                [type_overrides]
+                U128 = "b.u128()"
+            "#,
+        ),
+        ("src/models/mod.rs", ""),
+    ];
+
+    let root = setup_test_files(&temp_dir, &files);
+    let file = ConfigFile::discover(root.join("src/models"))?.expect("config file not found");
+
+    assert_eq!(file.path, root.join("zorsh.toml"));
+    assert_eq!(file.input, Some(root.join("src")));
+    assert_eq!(file.output, Some(root.join("generated")));
+    assert!(!file.config.only_annotated);
+    assert!(matches!(
+        file.config.output_structure,
+        OutputStructure::Flat
+    ));
+    assert_eq!(file.config.ignored_patterns, vec!["generated/".to_string()]);
+    assert_eq!(file.config.type_overrides["U128"], "b.u128()");
+
+    Ok(())
+}
+
+#[test]
+fn test_discover_cargo_metadata() -> Result<()> {
+    let temp_dir = setup_test_dir();
+
+    let files = vec![
+        (
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "models"
+                version = "0.1.0"
+
+                [package.metadata.zorsh]
+                input = "src/models"
+                output = "ts"
+            "#,
+        ),
+        (
+            "crates/inner/Cargo.toml",
+            r#"
+                [package]
+                name = "inner"
+                version = "0.1.0"
+            "#,
+        ),
+    ];
+
+    let root = setup_test_files(&temp_dir, &files);
+
+    // The inner manifest has no metadata table, so discovery keeps walking up
+    let file = ConfigFile::discover(root.join("crates/inner"))?.expect("config file not found");
+
+    assert_eq!(file.path, root.join("Cargo.toml"));
+    assert_eq!(file.input, Some(root.join("src/models")));
+    assert_eq!(file.output, Some(root.join("ts")));
+    assert!(file.config.only_annotated);
+    assert_eq!(
+        file.config.ignored_patterns,
+        Config::default().ignored_patterns
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_config_keys_are_rejected() -> Result<()> {
+    let temp_dir = setup_test_dir();
+
+    let root = setup_test_files(&temp_dir, &[("zorsh.toml", "only_anotated = false\n")]);
+    let err = ConfigFile::load(root.join("zorsh.toml")).unwrap_err();
+
+    assert!(format!("{:#}", err).contains("only_anotated"));
+
+    Ok(())
+}
+
+#[test]
+fn test_type_overrides() -> Result<()> {
+    let input = r#"
+        #[derive(BorshSerialize)]
+        struct Balance {
+            amount: U128,
+            owner: super::accounts::AccountId,
+        }
+    "#;
+
+    let mut config = Config::default();
+    config
+        .type_overrides
+        .insert("U128".to_string(), "b.u128()".to_string());
+    config
+        .type_overrides
+        .insert("accounts::AccountId".to_string(), "b.string()".to_string());
+
+    let output = ZorshGen::new(config).convert_str(input)?;
+
+    assert!(output.contains("amount: b.u128()"));
+    assert!(output.contains("owner: b.string()"));
+    assert!(!output.contains("import { AccountIdSchema }"));
+
+    Ok(())
+}
@@ -39,8 +39,10 @@ fn test_only_annotated_config() -> Result<()> {
     fs::create_dir(&output_dir)?;
 
     // Test with only_annotated = false
-    let mut config = Config::default();
-    config.only_annotated = false;
+    let config = Config {
+        only_annotated: false,
+        ..Config::default()
+    };
     let generator = ZorshGen::new(config);
     generator.convert(&input_dir, &output_dir)?;
 
@@ -89,8 +91,10 @@ fn test_ignored_patterns_config() -> Result<()> {
     let output_dir = temp_dir.path().join("output");
 
     // Test with custom ignored patterns
-    let mut config = Config::default();
-    config.ignored_patterns = vec!["generated/".to_string(), "test_utils/".to_string()];
+    let config = Config {
+        ignored_patterns: vec!["generated/".to_string(), "test_utils/".to_string()],
+        ..Config::default()
+    };
 
     let generator = ZorshGen::new(config);
     generator.convert(&input_dir, &output_dir)?;
@@ -168,3 +172,148 @@ fn test_output_structure_config() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_ignored_patterns_are_globs() -> Result<()> {
+    let temp_dir = setup_test_dir();
+
+    let files = vec![
+        (
+            "src/contests/entry.rs",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Entry {
+                    field: String,
+                }
+            "#,
+        ),
+        (
+            "tests/fixtures.rs",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Fixture {
+                    field: String,
+                }
+            "#,
+        ),
+        (
+            "src/models/user.rs",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct User {
+                    name: String,
+                }
+            "#,
+        ),
+        (
+            "src/models/user_test.rs",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct UserFixture {
+                    name: String,
+                }
+            "#,
+        ),
+    ];
+
+    let input_dir = setup_test_files(&temp_dir, &files);
+    let output_dir = temp_dir.path().join("output");
+
+    let config = Config {
+        ignored_patterns: vec!["tests/".to_string(), "*_test.rs".to_string()],
+        ..Config::default()
+    };
+    ZorshGen::new(config).convert(&input_dir, &output_dir)?;
+
+    // "tests/" only matches a directory named tests, not "contests"
+    assert!(output_dir.join("src/contests/entry.ts").exists());
+    assert!(output_dir.join("src/models/user.ts").exists());
+    assert!(!output_dir.join("tests/fixtures.ts").exists());
+    assert!(!output_dir.join("src/models/user_test.ts").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_include_patterns_config() -> Result<()> {
+    let temp_dir = setup_test_dir();
+
+    let files = vec![
+        (
+            "src/lib.rs",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Root {
+                    field: String,
+                }
+            "#,
+        ),
+        (
+            "src/models/item.rs",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Item {
+                    field: String,
+                }
+            "#,
+        ),
+    ];
+
+    let input_dir = setup_test_files(&temp_dir, &files);
+    let output_dir = temp_dir.path().join("output");
+
+    let config = Config {
+        include_patterns: vec!["src/models/**".to_string()],
+        ..Config::default()
+    };
+    ZorshGen::new(config).convert(&input_dir, &output_dir)?;
+
+    assert!(output_dir.join("src/models/item.ts").exists());
+    assert!(!output_dir.join("src/lib.ts").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_respect_gitignore_config() -> Result<()> {
+    let temp_dir = setup_test_dir();
+
+    let files = vec![
+        (".gitignore", "legacy/\n"),
+        (
+            "src/lib.rs",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Current {
+                    field: String,
+                }
+            "#,
+        ),
+        (
+            "src/legacy/old.rs",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Old {
+                    field: String,
+                }
+            "#,
+        ),
+    ];
+
+    let input_dir = setup_test_files(&temp_dir, &files);
+
+    let output_dir = temp_dir.path().join("all");
+    ZorshGen::new(Config::default()).convert(&input_dir, &output_dir)?;
+    assert!(output_dir.join("src/legacy/old.ts").exists());
+
+    let output_dir = temp_dir.path().join("filtered");
+    let config = Config {
+        respect_gitignore: true,
+        ..Config::default()
+    };
+    ZorshGen::new(config).convert(&input_dir, &output_dir)?;
+    assert!(output_dir.join("src/lib.ts").exists());
+    assert!(!output_dir.join("src/legacy/old.ts").exists());
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use super::*;
+use anyhow::Result;
+use std::path::PathBuf;
+use zorsh_gen_rs::{SourceFile, TypeDeclarations};
+
+fn sources() -> Vec<SourceFile> {
+    vec![SourceFile::in_memory(
+        "models",
+        r#"
+            /// A player of the game.
+            ///
+            /// Players are created on first login.
+            #[derive(BorshSerialize)]
+            pub struct Player {
+                /// Display name, not unique
+                name: String,
+                #[deprecated(since = "0.2.0", note = "use `status` instead")]
+                online: bool,
+                status: Status,
+            }
+
+            /** Where the player is */
+            #[derive(BorshSerialize)]
+            pub enum Status {
+                /// Not in a game
+                Idle,
+                /// Playing a match
+                Playing {
+                    /// Match identifier
+                    match_id: u64,
+                },
+                #[deprecated]
+                Away(u32),
+            }
+        "#,
+    )]
+}
+
+#[test]
+fn test_doc_comments_become_jsdoc() -> Result<()> {
+    let generated = generate(Config::default(), &sources())?;
+    insta::assert_snapshot!(generated.files[&PathBuf::from("models.ts")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_doc_comments_on_interfaces() -> Result<()> {
+    let generated = generate(
+        Config {
+            type_declarations: TypeDeclarations::Interfaces,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+    insta::assert_snapshot!(generated.files[&PathBuf::from("models.ts")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_doc_comments_in_ir() -> Result<()> {
+    let ir = ZorshGen::new(Config::default()).parse(&[SourceFile::in_memory(
+        "models",
+        r#"
+            /// Documented
+            #[derive(BorshSerialize)]
+            pub struct Documented {
+                #[deprecated = "gone soon"]
+                value: u8,
+            }
+
+            #[derive(BorshSerialize)]
+            pub struct Undocumented {
+                value: u8,
+            }
+        "#,
+    )])?;
+
+    let documented = ir.struct_map().remove("models::Documented").unwrap();
+    assert_eq!(documented.docs.lines, vec!["Documented"]);
+    assert_eq!(
+        documented.fields[0].docs.deprecated.as_deref(),
+        Some("gone soon")
+    );
+
+    // Items without docs serialize as before
+    let json = serde_json::to_string(&ir.struct_map()["models::Undocumented"])?;
+    assert!(!json.contains("docs"));
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use super::*;
+use anyhow::Result;
+use std::path::PathBuf;
+use zorsh_gen_rs::{SourceFile, TypeDeclarations};
+
+fn sources() -> Vec<SourceFile> {
+    vec![SourceFile::in_memory(
+        "models",
+        r#"
+            #[derive(BorshSerialize)]
+            pub struct Item {
+                id: u32,
+            }
+
+            #[derive(BorshSerialize)]
+            pub enum PlayerStatus {
+                Idle,
+                /// In a fight
+                Fighting { enemy: String, round: u8 },
+                Trading(Item),
+            }
+        "#,
+    )]
+}
+
+#[test]
+fn test_enum_helpers() -> Result<()> {
+    let generated = generate(
+        Config {
+            enum_helpers: true,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+    insta::assert_snapshot!(generated.files[&PathBuf::from("models.ts")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_enum_helpers_with_separate_types_file() -> Result<()> {
+    let generated = generate(
+        Config {
+            enum_helpers: true,
+            type_declarations: TypeDeclarations::SeparateFile,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+
+    // Helpers are plain TypeScript, so they live next to the types
+    let types = &generated.files[&PathBuf::from("models.types.ts")];
+    assert!(types.contains("export const PlayerStatus = {\n"));
+    assert!(types.contains("    Trading: (value: Item): PlayerStatus => ({ Trading: value }),\n"));
+
+    let schemas = &generated.files[&PathBuf::from("models.ts")];
+    assert!(schemas.contains("export { type Item, PlayerStatus } from './models.types';\n"));
+    assert!(!schemas.contains("export const PlayerStatus ="));
+
+    Ok(())
+}
+
+#[test]
+fn test_enum_helpers_are_off_by_default() -> Result<()> {
+    let generated = ZorshGen::new(Config::default()).generate(&[SourceFile::in_memory(
+        "models",
+        "#[derive(BorshSerialize)] pub enum Status { Idle }",
+    )])?;
+    assert!(!generated.files[&PathBuf::from("models.ts")].contains("export const Status ="));
+
+    Ok(())
+}
+
+#[test]
+fn test_enum_helpers_reject_clashing_variants() -> Result<()> {
+    let generator = ZorshGen::new(Config {
+        enum_helpers: true,
+        ..Config::default()
+    });
+
+    let error = generator
+        .generate(&[SourceFile::in_memory(
+            "models",
+            "#[derive(BorshSerialize)] pub enum Check { Valid, isValid }",
+        )])
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "The helpers of `Check` would define `isValid` twice, rename the variant or disable enum helpers"
+    );
+
+    // Rust can't name a variant `match`, but other inputs can
+    let ir = generator.parse(&[SourceFile::in_memory(
+        "models",
+        "#[derive(BorshSerialize)] pub enum Token { Word, Match }",
+    )])?;
+    let mut enums = ir.enum_map();
+    enums.get_mut("models::Token").unwrap().variants[1].name = "match".to_string();
+    let error = generator
+        .generate_ir(&TypeIr::new(ir.struct_map(), enums)?)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "The helpers of `Token` would define `match` twice, rename the variant or disable enum helpers"
+    );
+
+    Ok(())
+}
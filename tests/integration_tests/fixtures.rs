@@ -0,0 +1,172 @@
+use super::*;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use zorsh_gen_rs::fixtures::write_vectors;
+use zorsh_gen_rs::{FixtureGenerator, ModuleFormat, SourceFile};
+
+fn sources() -> Vec<SourceFile> {
+    vec![
+        SourceFile::in_memory(
+            "game::player",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Player {
+                    name: String,
+                    balance: u128,
+                    position: [i16; 2],
+                    guild: Option<String>,
+                    scores: HashMap<u8, u32>,
+                    status: Status,
+                }
+
+                #[derive(BorshSerialize)]
+                pub enum Status {
+                    Idle,
+                    Moving(i32, i32),
+                    Trading { with: String },
+                }
+            "#,
+        ),
+        SourceFile::in_memory(
+            "game::items",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Item {
+                    id: u64,
+                    price: Money,
+                }
+
+                #[derive(BorshSerialize)]
+                pub struct Money(u128);
+            "#,
+        ),
+    ]
+}
+
+fn setup_fixtures() -> TempDir {
+    let temp_dir = setup_test_dir();
+    setup_test_files(
+        &temp_dir,
+        &[
+            (
+                "game/player/Player/full.json",
+                r#"{
+                    "name": "Ann's",
+                    "balance": "340282366920938463463374607431768211455",
+                    "position": [-1, 2],
+                    "guild": "Knights",
+                    "scores": [[2, 20], [1, 10]],
+                    "status": { "Moving": [3, -4] }
+                }"#,
+            ),
+            (
+                "game/player/Player/empty.json",
+                r#"{
+                    "name": "",
+                    "balance": 0,
+                    "position": [0, 0],
+                    "guild": null,
+                    "scores": [],
+                    "status": "Idle"
+                }"#,
+            ),
+            (
+                "game::player::Status.json",
+                r#"{ "Trading": { "with": "Bob" } }"#,
+            ),
+        ],
+    );
+    temp_dir
+}
+
+#[test]
+fn test_fixture_suite() -> Result<()> {
+    let temp_dir = setup_fixtures();
+    let generator = FixtureGenerator::new(&parse(&sources())?, Config::default());
+    let fixtures = generator.load_dir(temp_dir.path())?;
+
+    let files: Vec<_> = fixtures.iter().map(|f| f.file.clone()).collect();
+    assert_eq!(
+        files,
+        vec![
+            PathBuf::from("game/player/Player/empty.json"),
+            PathBuf::from("game/player/Player/full.json"),
+            PathBuf::from("game::player::Status.json"),
+        ]
+    );
+    assert!(fixtures[..2]
+        .iter()
+        .all(|f| f.type_path == "game::player::Player"));
+    assert_eq!(fixtures[2].bytes, vec![2, 3, 0, 0, 0, b'B', b'o', b'b']);
+
+    let test_file = generator.test_file();
+    assert_eq!(test_file, PathBuf::from("fixtures.test.ts"));
+    insta::assert_snapshot!(generator.test_suite(&test_file, &fixtures));
+
+    Ok(())
+}
+
+#[test]
+fn test_vectors_are_written_next_to_fixtures() -> Result<()> {
+    let temp_dir = setup_fixtures();
+    let generator = FixtureGenerator::new(&parse(&sources())?, Config::default());
+    let fixtures = generator.load_dir(temp_dir.path())?;
+    write_vectors(temp_dir.path(), &fixtures)?;
+
+    let path = temp_dir.path().join("game::player::Status");
+    assert_eq!(
+        fs::read(path.with_extension("bin"))?,
+        vec![2, 3, 0, 0, 0, b'B', b'o', b'b']
+    );
+    assert_eq!(
+        fs::read_to_string(path.with_extension("hex"))?,
+        "0203000000426f62\n"
+    );
+    assert!(temp_dir.path().join("game/player/Player/full.bin").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_fixture_types_follow_the_configuration() -> Result<()> {
+    let config = Config {
+        module_format: ModuleFormat::CommonJs,
+        type_overrides: BTreeMap::from([("Money".to_string(), "b.u128()".to_string())]),
+        ..Config::default()
+    };
+    let generator = FixtureGenerator::new(&parse(&sources())?, config);
+    let fixture = generator.fixture(
+        Path::new("Item.json"),
+        &serde_json::json!({ "id": 1, "price": {} }),
+    )?;
+    assert_eq!(fixture.type_path, "game::items::Item");
+
+    let test_file = generator.test_file();
+    assert_eq!(test_file, PathBuf::from("fixtures.test.cts"));
+    let suite = generator.test_suite(&test_file, &[fixture]);
+    assert!(suite.contains("    // Item is not tested: the encoding of `Money` isn't known\n"));
+    assert!(!suite.contains("from './game/items'"));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_fixtures_are_rejected() -> Result<()> {
+    let generator = FixtureGenerator::new(&parse(&sources())?, Config::default());
+
+    let error = generator
+        .fixture(Path::new("game/Missing.json"), &serde_json::json!({}))
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Unknown type `game::Missing`");
+
+    let error = generator
+        .fixture(
+            Path::new("Status/bad.json"),
+            &serde_json::json!({ "Flying": {} }),
+        )
+        .unwrap_err();
+    assert!(format!("{:#}", error).contains("Unknown variant `Flying`"));
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use super::*;
+use anyhow::Result;
+use std::path::PathBuf;
+use zorsh_gen_rs::SourceFile;
+
+#[test]
+fn test_generate_in_memory_sources() -> Result<()> {
+    let sources = vec![
+        SourceFile::in_memory(
+            "models::player",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Player {
+                    name: String,
+                    item: super::item::Item,
+                }
+            "#,
+        ),
+        SourceFile::in_memory(
+            "models::item",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Item {
+                    id: u32,
+                }
+            "#,
+        ),
+    ];
+
+    let generated = ZorshGen::new(Config::default()).generate(&sources)?;
+
+    let paths: Vec<_> = generated.files.keys().cloned().collect();
+    assert_eq!(
+        paths,
+        vec![
+            PathBuf::from("models/item.ts"),
+            PathBuf::from("models/player.ts")
+        ]
+    );
+    assert!(generated.files[&PathBuf::from("models/player.ts")].contains("PlayerSchema"));
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_path_does_not_write() -> Result<()> {
+    let temp_dir = setup_test_dir();
+
+    let input_dir = setup_test_files(
+        &temp_dir,
+        &[(
+            "src/lib.rs",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Thing {
+                    field: String,
+                }
+            "#,
+        )],
+    );
+
+    let generated = ZorshGen::new(Config::default()).generate_path(&input_dir)?;
+
+    assert!(generated.files[&PathBuf::from("src/lib.ts")].contains("ThingSchema"));
+    assert_eq!(fs::read_dir(temp_dir.path())?.count(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_reports_unresolved_types() -> Result<()> {
+    let sources = vec![SourceFile::in_memory(
+        "models",
+        r#"
+            #[derive(BorshSerialize)]
+            pub struct Account {
+                id: AccountId,
+                balance: Vec<Balance>,
+            }
+
+            #[derive(BorshSerialize)]
+            pub struct Balance {
+                amount: u64,
+            }
+        "#,
+    )];
+
+    let generated = ZorshGen::new(Config::default()).generate(&sources)?;
+
+    assert_eq!(generated.diagnostics.len(), 1);
+    assert_eq!(generated.diagnostics[0].location, "models::Account.id");
+    assert_eq!(
+        generated.diagnostics[0].message,
+        "unresolved type `models::AccountId`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_is_deterministic() -> Result<()> {
+    let sources = vec![SourceFile::in_memory(
+        "root",
+        r#"
+            #[derive(BorshSerialize)]
+            pub struct A { b: B, c: C }
+            #[derive(BorshSerialize)]
+            pub struct B { value: u8 }
+            #[derive(BorshSerialize)]
+            pub struct C { value: u8 }
+            #[derive(BorshSerialize)]
+            pub struct D { value: u8 }
+        "#,
+    )];
+
+    let generator = ZorshGen::new(Config::default());
+    let first = generator.generate(&sources)?;
+    for _ in 0..10 {
+        assert_eq!(generator.generate(&sources)?.files, first.files);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use super::*;
+use anyhow::Result;
+use std::path::PathBuf;
+use zorsh_gen_rs::{BackendKind, Header, SourceFile, TypeDeclarations, ZorshConverter};
+
+const PLAYER: &str = r#"
+    #[derive(BorshSerialize)]
+    pub struct Player {
+        name: String,
+    }
+"#;
+
+fn header_config(hash: bool) -> Config {
+    Config {
+        header: Header {
+            enabled: true,
+            hash,
+            ..Header::default()
+        },
+        ..Config::default()
+    }
+}
+
+#[test]
+fn test_header_lists_version_and_sources() -> Result<()> {
+    let generated = ZorshGen::new(Config {
+        type_declarations: TypeDeclarations::SeparateFile,
+        barrels: true,
+        ..header_config(false)
+    })
+    .generate(&[SourceFile::in_memory("models::player", PLAYER)])?;
+
+    let expected = format!(
+        "// @generated by zorsh-gen-rs {}. Do not edit by hand, changes will be overwritten.\n\
+         // Sources: models/player.rs\n\n\
+         import {{ b }} from '@zorsh/zorsh';\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    let player = &generated.files[&PathBuf::from("models/player.ts")];
+    assert!(player.starts_with(&expected), "{}", player);
+
+    let types = &generated.files[&PathBuf::from("models/player.types.ts")];
+    assert!(types.contains("// Sources: models/player.rs\n"));
+
+    // Barrels don't come from any source
+    let barrel = &generated.files[&PathBuf::from("models/index.ts")];
+    assert!(barrel.starts_with("// @generated by zorsh-gen-rs"));
+    assert!(!barrel.contains("Sources:"));
+
+    Ok(())
+}
+
+#[test]
+fn test_header_follows_the_output_format() -> Result<()> {
+    let sources = [SourceFile::in_memory("models::player", PLAYER)];
+
+    let generated = ZorshGen::new(Config {
+        backend: BackendKind::Python,
+        ..header_config(true)
+    })
+    .generate(&sources)?;
+    let player = &generated.files[&PathBuf::from("models/player.py")];
+    assert!(player.starts_with("# @generated by zorsh-gen-rs"));
+    assert!(player.contains("\n# Hash: sha256:"));
+
+    // JSON has no comments to put a header in
+    let generated = ZorshGen::new(Config {
+        backend: BackendKind::JsonSchema,
+        ..header_config(true)
+    })
+    .generate(&sources)?;
+    assert!(generated.files[&PathBuf::from("models/player.schema.json")].starts_with('{'));
+
+    // Disabled by default
+    let generated = ZorshGen::new(Config::default()).generate(&sources)?;
+    assert!(generated.files[&PathBuf::from("models/player.ts")].starts_with("import"));
+
+    Ok(())
+}
+
+#[test]
+fn test_check_reports_outdated_files() -> Result<()> {
+    let temp_dir = setup_test_dir();
+    let input_dir = setup_test_files(&temp_dir, &[("models/player.rs", PLAYER)]).join("models");
+    let output_dir = temp_dir.path().join("generated");
+    let converter = ZorshConverter::new(&input_dir, &output_dir, header_config(true));
+
+    let problems = converter.check()?;
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].to_string(), "player.ts: missing");
+
+    converter.convert()?;
+    assert!(converter.check()?.is_empty());
+
+    // The hash tells hand edits apart from stale output
+    let player_path = output_dir.join("player.ts");
+    let generated = fs::read_to_string(&player_path)?;
+    fs::write(&player_path, generated.replace("b.string()", "b.u8()"))?;
+    assert_eq!(
+        converter.check()?[0].to_string(),
+        "player.ts: edited by hand"
+    );
+
+    converter.convert()?;
+    fs::write(
+        input_dir.join("player.rs"),
+        PLAYER.replace("name: String", "name: u8"),
+    )?;
+    assert_eq!(converter.check()?[0].to_string(), "player.ts: out of date");
+
+    fs::remove_file(input_dir.join("player.rs"))?;
+    assert_eq!(
+        converter.check()?[0].to_string(),
+        "player.ts: no longer generated"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_check_in_another_checkout() -> Result<()> {
+    // Paths from zorsh.toml are absolute, so both checkouts use absolute ones
+    let checkouts = [setup_test_dir(), setup_test_dir()];
+    let converters: Vec<_> = checkouts
+        .iter()
+        .map(|checkout| {
+            let root = setup_test_files(checkout, &[("src/player.rs", PLAYER)]);
+            ZorshConverter::new(
+                root.join("src"),
+                root.join("generated"),
+                header_config(true),
+            )
+        })
+        .collect();
+
+    converters[0].convert()?;
+    let generated = checkouts[0].path().join("generated");
+    let player = fs::read_to_string(generated.join("player.ts"))?;
+    assert!(player.contains("// Sources: player.rs\n"));
+
+    let copy = checkouts[1].path().join("generated");
+    fs::create_dir_all(&copy)?;
+    for entry in fs::read_dir(&generated)? {
+        let entry = entry?;
+        fs::copy(entry.path(), copy.join(entry.file_name()))?;
+    }
+    assert!(converters[1].check()?.is_empty());
+
+    Ok(())
+}
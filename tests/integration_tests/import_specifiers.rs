@@ -0,0 +1,140 @@
+use super::*;
+use anyhow::Result;
+use std::path::PathBuf;
+use zorsh_gen_rs::{ImportExtension, ModuleFormat, OutputStructure, SourceFile, TypeDeclarations};
+
+fn sources() -> Vec<SourceFile> {
+    vec![
+        SourceFile::in_memory(
+            "models::player",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Player {
+                    item: super::items::Item,
+                    owner: crate::accounts::Account,
+                }
+            "#,
+        ),
+        SourceFile::in_memory(
+            "models::items",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Item {
+                    id: u32,
+                }
+            "#,
+        ),
+        SourceFile::in_memory(
+            "accounts",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Account {
+                    name: String,
+                }
+            "#,
+        ),
+    ]
+}
+
+#[test]
+fn test_imports_are_relative_to_the_importing_file() -> Result<()> {
+    let generated = generate(Config::default(), &sources())?;
+    let player = &generated.files[&PathBuf::from("models/player.ts")];
+    assert!(player.contains("import { AccountSchema } from '../accounts';\n"));
+    assert!(player.contains("import { ItemSchema } from './items';\n"));
+
+    let generated = generate(
+        Config {
+            output_structure: OutputStructure::Flat,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+    let player = &generated.files[&PathBuf::from("models_player.ts")];
+    assert!(player.contains("import { ItemSchema } from './models_items';\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_import_extensions() -> Result<()> {
+    let generated = generate(
+        Config {
+            import_extension: ImportExtension::Js,
+            barrels: true,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+    let player = &generated.files[&PathBuf::from("models/player.ts")];
+    assert!(player.contains("import { AccountSchema } from '../accounts.js';\n"));
+    assert!(player.contains("import { ItemSchema } from './items.js';\n"));
+    assert_eq!(
+        generated.files[&PathBuf::from("index.ts")],
+        "export * from './accounts.js';\nexport * from './models/index.js';\n"
+    );
+
+    let generated = generate(
+        Config {
+            import_extension: ImportExtension::Ts,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+    let player = &generated.files[&PathBuf::from("models/player.ts")];
+    assert!(player.contains("import { ItemSchema } from './items.ts';\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_commonjs_files() -> Result<()> {
+    let generated = generate(
+        Config {
+            module_format: ModuleFormat::CommonJs,
+            import_extension: ImportExtension::Js,
+            type_declarations: TypeDeclarations::SeparateFile,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+
+    let paths: Vec<_> = generated.files.keys().cloned().collect();
+    assert_eq!(
+        paths,
+        vec![
+            PathBuf::from("accounts.cts"),
+            PathBuf::from("accounts.types.cts"),
+            PathBuf::from("models/items.cts"),
+            PathBuf::from("models/items.types.cts"),
+            PathBuf::from("models/player.cts"),
+            PathBuf::from("models/player.types.cts"),
+        ]
+    );
+
+    let player = &generated.files[&PathBuf::from("models/player.cts")];
+    assert!(player.contains("import { ItemSchema } from './items.cjs';\n"));
+    assert!(player.contains("export type { Player } from './player.types.cjs';\n"));
+    let player_types = &generated.files[&PathBuf::from("models/player.types.cts")];
+    assert!(player_types.contains("import type { Account } from '../accounts.types.cjs';\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_interfaces_import_types() -> Result<()> {
+    let generated = generate(
+        Config {
+            type_declarations: TypeDeclarations::Interfaces,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+
+    let player = &generated.files[&PathBuf::from("models/player.ts")];
+    assert!(player
+        .contains("import { ItemSchema } from './items';\nimport type { Item } from './items';\n"));
+    assert!(player.contains("    item: Item;\n"));
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use super::*;
+use anyhow::Result;
+use zorsh_gen_rs::{SourceFile, TypeIr, ZorshConverter};
+
+const MODELS: &str = r#"
+    #[derive(BorshSerialize)]
+    pub struct Player {
+        name: String,
+        inventory: HashMap<String, Vec<Item>>,
+        status: Option<Status>,
+    }
+
+    #[derive(BorshSerialize)]
+    pub struct Item {
+        id: u32,
+        tags: [u8; 4],
+    }
+
+    #[derive(BorshSerialize)]
+    pub enum Status {
+        Idle,
+        Fighting { enemy: String },
+    }
+"#;
+
+#[test]
+fn test_ir_json_round_trip() -> Result<()> {
+    let generator = ZorshGen::new(Config::default());
+    let sources = vec![SourceFile::in_memory("models", MODELS)];
+
+    let ir = generator.parse(&sources)?;
+    let json = ir.to_json()?;
+    let loaded = TypeIr::from_json(&json)?;
+
+    assert_eq!(loaded.to_json()?, json);
+    assert_eq!(
+        generator.generate_ir(&loaded)?.files,
+        generator.generate(&sources)?.files
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_ir_json_shape() -> Result<()> {
+    let ir = ZorshGen::new(Config::default()).parse(&[SourceFile::in_memory("models", MODELS)])?;
+    let json: serde_json::Value = serde_json::from_str(&ir.to_json()?)?;
+
+    // Types are sorted by path so dumps are stable
+    assert_eq!(json["structs"][0]["name"], "Item");
+    assert_eq!(json["structs"][1]["name"], "Player");
+    assert_eq!(json["enums"][0]["module_path"], "models");
+    assert_eq!(
+        json["structs"][0]["fields"][1]["type_kind"],
+        serde_json::json!({ "Array": [{ "Primitive": "u8" }, 4] })
+    );
+    assert_eq!(
+        json["dependencies"]["ordered_types"]
+            .as_array()
+            .map(|types| types.len()),
+        Some(3)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_converter_emits_and_reads_ir() -> Result<()> {
+    let temp_dir = setup_test_dir();
+
+    let input_dir = setup_test_files(&temp_dir, &[("src/models.rs", MODELS)]);
+    let ir_path = temp_dir.path().join("ir.json");
+    let from_source = temp_dir.path().join("from_source");
+    let from_ir = temp_dir.path().join("from_ir");
+
+    ZorshConverter::new(&input_dir, &from_source, Config::default())
+        .with_ir_output(&ir_path)
+        .convert()?;
+    assert!(ir_path.exists());
+
+    ZorshConverter::from_ir(&ir_path, &from_ir, Config::default()).convert()?;
+
+    assert_eq!(
+        fs::read_to_string(from_ir.join("src/models.ts"))?,
+        fs::read_to_string(from_source.join("src/models.ts"))?
+    );
+
+    Ok(())
+}
@@ -1,13 +1,33 @@
+mod backends;
+mod barrels;
 mod basic_types;
+mod borsh_codec;
+mod borsh_schema_input;
 mod complex_types;
+mod config_file;
 mod config_tests;
+mod doc_comments;
+mod enum_helpers;
+mod fixtures;
+mod generation_api;
+mod headers;
+mod import_specifiers;
+mod ir_tests;
 mod module_structure;
+mod naming;
+mod near_abi;
+mod output_cleanup;
+mod test_suites;
+mod tuple_variants;
 mod type_aliases;
+mod type_declarations;
+mod type_sizes;
 
 // Shared test utilities
+use anyhow::Result;
 use std::fs;
 use tempfile::TempDir;
-use zorsh_gen_rs::{Config, ZorshGen};
+use zorsh_gen_rs::{Config, GeneratedFiles, SourceFile, TypeIr, ZorshGen};
 
 // Helper to setup temporary test directories
 pub(crate) fn setup_test_dir() -> TempDir {
@@ -30,3 +50,13 @@ pub(crate) fn setup_test_files(temp_dir: &TempDir, files: &[(&str, &str)]) -> st
     }
     temp_dir.path().to_path_buf()
 }
+
+// Helper to generate the files of in-memory source modules
+pub(crate) fn generate(config: Config, sources: &[SourceFile]) -> Result<GeneratedFiles> {
+    ZorshGen::new(config).generate(sources)
+}
+
+// Helper to parse in-memory source modules with the default configuration
+pub(crate) fn parse(sources: &[SourceFile]) -> Result<TypeIr> {
+    ZorshGen::new(Config::default()).parse(sources)
+}
@@ -143,3 +143,59 @@ fn test_module_imports() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_relative_type_paths() -> Result<()> {
+    use zorsh_gen_rs::type_parser::TypeKind;
+    use zorsh_gen_rs::SourceFile;
+
+    let source = |module, code| SourceFile::in_memory(module, code);
+    let ir = ZorshGen::new(Config::default()).parse(&[
+        source(
+            "models::player",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Player {
+                    sibling: super::items::Item,
+                    child: self::stats::Stats,
+                    root: crate::accounts::Account,
+                    parent: super::super::accounts::Account,
+                    absolute: models::items::Item,
+                }
+            "#,
+        ),
+        source(
+            "models::player::stats",
+            "#[derive(BorshSerialize)] pub struct Stats { level: u8 }",
+        ),
+        source(
+            "models::items",
+            "#[derive(BorshSerialize)] pub struct Item { id: u32 }",
+        ),
+        source(
+            "accounts",
+            "#[derive(BorshSerialize)] pub struct Account { id: u32 }",
+        ),
+    ])?;
+
+    let player = &ir.struct_map()["models::player::Player"];
+    let paths: Vec<_> = player
+        .fields
+        .iter()
+        .map(|field| match &field.type_kind {
+            TypeKind::Struct(_, path) => path.as_str(),
+            other => panic!("Unexpected type: {:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "models::items::Item",
+            "models::player::stats::Stats",
+            "accounts::Account",
+            "accounts::Account",
+            "models::items::Item",
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use super::*;
+use anyhow::Result;
+use std::path::PathBuf;
+use zorsh_gen_rs::{Case, Naming, SourceFile, TypeDeclarations};
+
+fn sources() -> Vec<SourceFile> {
+    vec![
+        SourceFile::in_memory(
+            "models::player",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Player {
+                    display_name: String,
+                    main_item: super::items::Item,
+                    status: Status,
+                }
+
+                #[derive(BorshSerialize)]
+                pub enum Status {
+                    Active,
+                    OnQuest { quest_id: u32 },
+                }
+            "#,
+        ),
+        SourceFile::in_memory(
+            "models::items",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Item {
+                    item_id: u32,
+                }
+            "#,
+        ),
+    ]
+}
+
+fn custom_naming() -> Config {
+    Config {
+        naming: Naming {
+            schema_name: "{name}Schema".to_string(),
+            type_name: "{Name}Codec".to_string(),
+            field_case: Case::Camel,
+            variant_case: Case::Snake,
+        },
+        ..Config::default()
+    }
+}
+
+#[test]
+fn test_custom_naming() -> Result<()> {
+    let generated = generate(custom_naming(), &sources())?;
+    insta::assert_snapshot!(generated.files[&PathBuf::from("models/player.ts")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_custom_naming_applies_to_imports() -> Result<()> {
+    let generated = generate(
+        Config {
+            type_declarations: TypeDeclarations::Interfaces,
+            barrels: true,
+            ..custom_naming()
+        },
+        &sources(),
+    )?;
+
+    let player = &generated.files[&PathBuf::from("models/player.ts")];
+    assert!(player.contains("import { itemSchema } from './items';\n"));
+    assert!(player.contains("import type { ItemCodec } from './items';\n"));
+    assert!(player.contains("    mainItem: ItemCodec;\n"));
+
+    let barrel = &generated.files[&PathBuf::from("models/index.ts")];
+    assert!(!barrel.contains("ItemSchema"));
+
+    Ok(())
+}
+
+#[test]
+fn test_case_conversion() {
+    assert_eq!(Case::Camel.apply("display_name"), "displayName");
+    assert_eq!(Case::Snake.apply("OnQuest"), "on_quest");
+    assert_eq!(Case::Pascal.apply("display_name"), "DisplayName");
+    assert_eq!(Case::Preserve.apply("display_name"), "display_name");
+    // Positional keys are left alone, and other leading underscores are kept
+    assert_eq!(Case::Camel.apply("_0"), "_0");
+    assert_eq!(Case::Camel.apply("_private_key"), "_privateKey");
+    assert_eq!(Case::Pascal.apply("__raw_id"), "__RawId");
+    assert_eq!(Case::Snake.apply("_"), "_");
+}
+
+#[test]
+fn test_case_collisions_are_rejected() {
+    let generate =
+        |source: &str| super::generate(custom_naming(), &[SourceFile::in_memory("models", source)]);
+
+    let error =
+        generate("#[derive(BorshSerialize)] pub struct Settings { fooBar: u8, foo_bar: u8 }")
+            .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`fooBar` and `foo_bar` of `Settings` would both be keyed `fooBar`, choose another case"
+    );
+
+    let error =
+        generate("#[derive(BorshSerialize)] pub enum Event { Login { user_id: u8, userId: u8 } }")
+            .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`user_id` and `userId` of `Event::Login` would both be keyed `userId`, choose another case"
+    );
+
+    let error =
+        generate("#[derive(BorshSerialize)] pub enum Mode { OnQuest, on_quest }").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`OnQuest` and `on_quest` of `Mode` would both be keyed `on_quest`, choose another case"
+    );
+}
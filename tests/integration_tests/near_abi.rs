@@ -0,0 +1,211 @@
+use super::*;
+use anyhow::Result;
+use zorsh_gen_rs::{NearAbi, TypeDeclarations};
+
+const ABI: &str = r#"{
+  "schema_version": "0.4.0",
+  "metadata": { "name": "token", "version": "0.1.0" },
+  "body": {
+    "functions": [
+      {
+        "name": "ft_balance_of",
+        "kind": "view",
+        "params": {
+          "serialization_type": "borsh",
+          "args": [
+            {
+              "name": "account_id",
+              "type_schema": {
+                "declaration": "String",
+                "definitions": {
+                  "String": {
+                    "Sequence": {
+                      "length_width": 4,
+                      "length_range": { "start": 0, "end": 4294967295 },
+                      "elements": "u8"
+                    }
+                  },
+                  "u8": { "Primitive": 1 }
+                }
+              }
+            }
+          ]
+        },
+        "result": {
+          "serialization_type": "borsh",
+          "type_schema": {
+            "declaration": "u128",
+            "definitions": { "u128": { "Primitive": 16 } }
+          }
+        }
+      },
+      {
+        "name": "ft_metadata",
+        "kind": "view",
+        "result": {
+          "serialization_type": "borsh",
+          "type_schema": {
+            "declaration": "Metadata",
+            "definitions": {
+              "Metadata": {
+                "Struct": {
+                  "fields": { "NamedFields": [["symbol", "String"], ["decimals", "u8"]] }
+                }
+              },
+              "String": {
+                "Sequence": {
+                  "length_width": 4,
+                  "length_range": { "start": 0, "end": 4294967295 },
+                  "elements": "u8"
+                }
+              },
+              "u8": { "Primitive": 1 }
+            }
+          }
+        }
+      },
+      {
+        "name": "set_metadata",
+        "kind": "call",
+        "params": {
+          "serialization_type": "borsh",
+          "args": [
+            {
+              "name": "metadata",
+              "type_schema": {
+                "declaration": "Metadata",
+                "definitions": {
+                  "Metadata": {
+                    "Struct": {
+                      "fields": { "NamedFields": [["symbol", "String"], ["decimals", "u8"]] }
+                    }
+                  },
+                  "String": {
+                    "Sequence": {
+                      "length_width": 4,
+                      "length_range": { "start": 0, "end": 4294967295 },
+                      "elements": "u8"
+                    }
+                  },
+                  "u8": { "Primitive": 1 }
+                }
+              }
+            }
+          ]
+        },
+        "result": {
+          "serialization_type": "borsh",
+          "type_schema": { "declaration": "()", "definitions": { "()": { "Primitive": 0 } } }
+        }
+      },
+      {
+        "name": "ft_transfer",
+        "kind": "call",
+        "params": {
+          "serialization_type": "json",
+          "args": [{ "name": "receiver_id", "type_schema": { "type": "string" } }]
+        }
+      }
+    ],
+    "root_schema": {}
+  }
+}"#;
+
+#[test]
+fn test_near_abi_schemas() -> Result<()> {
+    let (output, diagnostics) = NearAbi::from_json(ABI)?.generate(&Config::default())?;
+
+    insta::assert_snapshot!(output);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "ft_transfer: arguments are JSON-serialized, skipping them"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_near_abi_uses_config() -> Result<()> {
+    let mut config = Config {
+        type_declarations: TypeDeclarations::Interfaces,
+        ..Config::default()
+    };
+    config.naming.schema_name = "{name}Codec".to_string();
+    let (output, _) = NearAbi::from_json(ABI)?.generate(&config)?;
+
+    assert!(output.contains("export const metadataCodec = b.struct({"));
+    assert!(output.contains("export interface SetMetadataArgs {"));
+    assert!(output.contains("export const ftBalanceOfResultCodec = b.u128();"));
+    assert!(output.contains("export type FtBalanceOfResult = bigint;"));
+    assert!(!output.contains("b.infer"));
+
+    config.type_declarations = TypeDeclarations::SeparateFile;
+    let error = NearAbi::from_json(ABI)?.generate(&config).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Type declarations in a separate file aren't supported for ABIs, use `interfaces`"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_near_abi_rejects_name_collisions() -> Result<()> {
+    let struct_schema = |name: &str| {
+        format!(
+            r#"{{ "declaration": "{0}", "definitions": {{
+                "{0}": {{ "Struct": {{ "fields": {{ "NamedFields": [["value", "u8"]] }} }} }},
+                "u8": {{ "Primitive": 1 }}
+            }} }}"#,
+            name
+        )
+    };
+    let abi = |functions: &[String]| {
+        format!(
+            r#"{{ "schema_version": "0.4.0", "metadata": {{}},
+                "body": {{ "functions": [{}], "root_schema": {{}} }} }}"#,
+            functions.join(", ")
+        )
+    };
+    let args = |function: &str, type_schema: &str| {
+        format!(
+            r#"{{ "name": "{}", "kind": "call", "params": {{ "serialization_type": "borsh",
+                "args": [{{ "name": "value", "type_schema": {} }}] }} }}"#,
+            function, type_schema
+        )
+    };
+    let result = |function: &str, type_schema: &str| {
+        format!(
+            r#"{{ "name": "{}", "kind": "view", "result": {{ "serialization_type": "borsh",
+                "type_schema": {} }} }}"#,
+            function, type_schema
+        )
+    };
+
+    let error = NearAbi::from_json(&abi(&[args("store", &struct_schema("StoreArgs"))]))?
+        .generate(&Config::default())
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`StoreArgs` is both a contract type and the arguments of `store`"
+    );
+
+    let error = NearAbi::from_json(&abi(&[result("get", &struct_schema("GetResult"))]))?
+        .generate(&Config::default())
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`GetResult` is both a contract type and the result of `get`"
+    );
+
+    let error = NearAbi::from_json(&abi(&[
+        args("get_value", &struct_schema("Value")),
+        args("getValue", &struct_schema("Value")),
+    ]))?
+    .generate(&Config::default())
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`GetValueArgs` is both the arguments of `get_value` and the arguments of `getValue`"
+    );
+    Ok(())
+}
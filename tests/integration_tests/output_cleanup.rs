@@ -0,0 +1,100 @@
+use super::*;
+use anyhow::Result;
+use zorsh_gen_rs::manifest::MANIFEST_FILE_NAME;
+
+const USER: &str = r#"
+    #[derive(BorshSerialize)]
+    pub struct User {
+        name: String,
+    }
+"#;
+
+const POST: &str = r#"
+    #[derive(BorshSerialize)]
+    pub struct Post {
+        title: String,
+    }
+"#;
+
+#[test]
+fn test_removed_modules_are_cleaned_up() -> Result<()> {
+    let temp_dir = setup_test_dir();
+
+    let input_dir = setup_test_files(
+        &temp_dir,
+        &[("src/models/user.rs", USER), ("src/posts/post.rs", POST)],
+    );
+    let output_dir = temp_dir.path().join("generated");
+
+    let generator = ZorshGen::new(Config::default());
+    generator.convert(&input_dir, &output_dir)?;
+
+    assert!(output_dir.join(MANIFEST_FILE_NAME).exists());
+    assert!(output_dir.join("src/posts/post.ts").exists());
+
+    // A file we did not generate must survive
+    fs::write(output_dir.join("src/posts/handwritten.ts"), "export {};\n")?;
+    fs::write(output_dir.join("custom.ts"), "export {};\n")?;
+
+    fs::remove_file(input_dir.join("src/posts/post.rs"))?;
+    generator.convert(&input_dir, &output_dir)?;
+
+    assert!(output_dir.join("src/models/user.ts").exists());
+    assert!(!output_dir.join("src/posts/post.ts").exists());
+    assert!(output_dir.join("src/posts/handwritten.ts").exists());
+    assert!(output_dir.join("custom.ts").exists());
+
+    let manifest = fs::read_to_string(output_dir.join(MANIFEST_FILE_NAME))?;
+    assert!(manifest.contains("src/models/user.ts"));
+    assert!(!manifest.contains("src/posts/post.ts"));
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_directories_are_removed() -> Result<()> {
+    let temp_dir = setup_test_dir();
+
+    let input_dir = setup_test_files(
+        &temp_dir,
+        &[("src/models/user.rs", USER), ("src/posts/post.rs", POST)],
+    );
+    let output_dir = temp_dir.path().join("generated");
+
+    let generator = ZorshGen::new(Config::default());
+    generator.convert(&input_dir, &output_dir)?;
+
+    fs::remove_dir_all(input_dir.join("src/posts"))?;
+    generator.convert(&input_dir, &output_dir)?;
+
+    assert!(!output_dir.join("src/posts").exists());
+    assert!(output_dir.join("src/models").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_clean_output_config() -> Result<()> {
+    let temp_dir = setup_test_dir();
+
+    let input_dir = setup_test_files(&temp_dir, &[("src/models/user.rs", USER)]);
+    let output_dir = temp_dir.path().join("generated");
+
+    ZorshGen::new(Config::default()).convert(&input_dir, &output_dir)?;
+
+    // Hand edits to a generated file are discarded by a clean run
+    fs::write(output_dir.join("src/models/user.ts"), "edited")?;
+    fs::write(output_dir.join("keep.ts"), "export {};\n")?;
+
+    let config = Config {
+        clean_output: true,
+        ..Config::default()
+    };
+    ZorshGen::new(config).convert(&input_dir, &output_dir)?;
+
+    let content = fs::read_to_string(output_dir.join("src/models/user.ts"))?;
+    assert!(content.contains("UserSchema"));
+    assert!(output_dir.join("keep.ts").exists());
+
+    Ok(())
+}
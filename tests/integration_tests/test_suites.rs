@@ -0,0 +1,115 @@
+use super::*;
+use anyhow::Result;
+use std::path::PathBuf;
+use zorsh_gen_rs::{SourceFile, TestSuites};
+
+fn sources() -> Vec<SourceFile> {
+    vec![
+        SourceFile::in_memory(
+            "game::player",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Player {
+                    name: String,
+                    balance: u128,
+                    position: [i16; 2],
+                    key: [u8; 32],
+                    guild: Option<String>,
+                    inventory: Vec<super::items::Item>,
+                    scores: HashMap<String, u32>,
+                    status: Status,
+                }
+
+                #[derive(BorshSerialize)]
+                pub enum Status {
+                    Idle,
+                    Moving(i32, i32),
+                    Trading { with: String, offer: Option<u64> },
+                }
+            "#,
+        ),
+        SourceFile::in_memory(
+            "game::items",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Item {
+                    id: u64,
+                }
+
+                #[derive(BorshSerialize)]
+                pub struct Listing {
+                    item: Item,
+                    price: Money,
+                }
+            "#,
+        ),
+    ]
+}
+
+#[test]
+fn test_round_trip_suites() -> Result<()> {
+    let generated = generate(
+        Config {
+            test_suites: TestSuites::Vitest,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+    insta::assert_snapshot!(generated.files[&PathBuf::from("game/player.test.ts")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_types_are_not_tested() -> Result<()> {
+    let generated = generate(
+        Config {
+            test_suites: TestSuites::Jest,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+
+    let items = &generated.files[&PathBuf::from("game/items.test.ts")];
+    assert!(items.starts_with("import { describe, expect, it } from '@jest/globals';\n"));
+    assert!(items.ends_with("\n// Listing is not tested: the encoding of `Money` isn't known\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_enums_without_variants_are_not_tested() -> Result<()> {
+    let generated = ZorshGen::new(Config {
+        test_suites: TestSuites::Vitest,
+        ..Config::default()
+    })
+    .generate(&[SourceFile::in_memory(
+        "events",
+        r#"
+            #[derive(BorshSerialize)]
+            pub enum Never {}
+
+            #[derive(BorshSerialize)]
+            pub struct Event {
+                never: Never,
+            }
+        "#,
+    )])?;
+
+    let events = &generated.files[&PathBuf::from("events.test.ts")];
+    assert!(events.contains("\n// Never is not tested: `Never` has no variants to sample\n"));
+    assert!(events.ends_with("\n// Event is not tested: `Never` has no variants to sample\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_no_suites_by_default() -> Result<()> {
+    let generated = generate(Config::default(), &sources())?;
+    assert!(!generated
+        .files
+        .keys()
+        .any(|path| path.to_string_lossy().ends_with(".test.ts")));
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use super::*;
+use anyhow::Result;
+use std::path::PathBuf;
+use zorsh_gen_rs::{SourceFile, TupleVariants, TypeDeclarations};
+
+fn sources() -> Vec<SourceFile> {
+    vec![SourceFile::in_memory(
+        "actions",
+        r#"
+            #[derive(BorshSerialize)]
+            pub enum Action {
+                Wait,
+                Say(String),
+                Move(i32, i32),
+                Attack { target: u32, damage: u16 },
+            }
+        "#,
+    )]
+}
+
+#[test]
+fn test_tuple_variants_as_tuples() -> Result<()> {
+    let generated = generate(
+        Config {
+            type_declarations: TypeDeclarations::Interfaces,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+    insta::assert_snapshot!(generated.files[&PathBuf::from("actions.ts")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_tuple_variants_as_structs() -> Result<()> {
+    let generated = generate(
+        Config {
+            type_declarations: TypeDeclarations::Interfaces,
+            tuple_variants: TupleVariants::Struct,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+    insta::assert_snapshot!(generated.files[&PathBuf::from("actions.ts")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_tuple_variant_helpers() -> Result<()> {
+    let generated = generate(
+        Config {
+            enum_helpers: true,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+    let actions = &generated.files[&PathBuf::from("actions.ts")];
+    assert!(actions.contains("    Move: (value: [number, number]): Action => ({ Move: value }),\n"));
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use super::*;
+use anyhow::Result;
+use std::path::PathBuf;
+use zorsh_gen_rs::{SourceFile, TypeDeclarations};
+
+#[test]
+fn test_interface_declarations() -> Result<()> {
+    let input = r#"
+        #[derive(BorshSerialize)]
+        struct Item {
+            id: u64,
+            weight: f32,
+            tags: [u8; 4],
+        }
+
+        #[derive(BorshSerialize)]
+        enum Status {
+            Idle,
+            Moving(i128),
+            Fighting { enemy: String, active: bool },
+        }
+
+        #[derive(BorshSerialize)]
+        struct Player {
+            name: String,
+            items: Vec<Option<Item>>,
+            scores: HashMap<String, u32>,
+            status: Option<Status>,
+        }
+    "#;
+
+    let config = Config {
+        type_declarations: TypeDeclarations::Interfaces,
+        ..Config::default()
+    };
+    let output = ZorshGen::new(config).convert_str(input)?;
+    insta::assert_snapshot!(output);
+    Ok(())
+}
+
+#[test]
+fn test_separate_type_files() -> Result<()> {
+    let sources = [
+        SourceFile::in_memory(
+            "player",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Player {
+                    name: String,
+                    item: items::Item,
+                }
+            "#,
+        ),
+        SourceFile::in_memory(
+            "items",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Item {
+                    id: u32,
+                }
+            "#,
+        ),
+    ];
+
+    let config = Config {
+        type_declarations: TypeDeclarations::SeparateFile,
+        ..Config::default()
+    };
+    let generated = ZorshGen::new(config).generate(&sources)?;
+
+    let paths: Vec<_> = generated.files.keys().cloned().collect();
+    assert_eq!(
+        paths,
+        vec![
+            PathBuf::from("items.ts"),
+            PathBuf::from("items.types.ts"),
+            PathBuf::from("player.ts"),
+            PathBuf::from("player.types.ts"),
+        ]
+    );
+
+    // The schema file re-exports the types, so importing from it keeps working
+    let player = &generated.files[&PathBuf::from("player.ts")];
+    assert!(player.contains("export type { Player } from './player.types';\n"));
+    assert!(!player.contains("b.infer"));
+
+    // The types file stands alone, without `@zorsh/zorsh`
+    insta::assert_snapshot!(generated.files[&PathBuf::from("player.types.ts")]);
+    Ok(())
+}
+
+#[test]
+fn test_interfaces_import_other_modules_types() -> Result<()> {
+    let sources = [
+        SourceFile::in_memory(
+            "player",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Player {
+                    item: items::Item,
+                    status: items::Status,
+                }
+            "#,
+        ),
+        SourceFile::in_memory(
+            "items",
+            r#"
+                #[derive(BorshSerialize)]
+                pub struct Item {
+                    id: u32,
+                }
+
+                #[derive(BorshSerialize)]
+                pub enum Status {
+                    Stored,
+                    Equipped,
+                }
+            "#,
+        ),
+    ];
+
+    let config = Config {
+        type_declarations: TypeDeclarations::Interfaces,
+        ..Config::default()
+    };
+    let generated = ZorshGen::new(config).generate(&sources)?;
+
+    let player = &generated.files[&PathBuf::from("player.ts")];
+    assert!(player.contains("import { ItemSchema, StatusSchema } from './items';\n"));
+    assert!(player.contains("import type { Item, Status } from './items';\n"));
+    assert!(player.contains("    item: Item;\n    status: Status;\n"));
+
+    let items = &generated.files[&PathBuf::from("items.ts")];
+    assert!(!items.contains("import type"));
+    Ok(())
+}
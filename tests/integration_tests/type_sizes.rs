@@ -0,0 +1,180 @@
+use super::*;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use zorsh_gen_rs::type_parser::TypeKind;
+use zorsh_gen_rs::{Size, SizeAnalyzer, SourceFile};
+
+const SOURCE: &str = r#"
+    #[derive(BorshSerialize)]
+    pub struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(BorshSerialize)]
+    pub enum Direction {
+        North,
+        East,
+    }
+
+    #[derive(BorshSerialize)]
+    pub enum Shape {
+        Dot(Point),
+        Line { from: Point, to: Point },
+    }
+
+    #[derive(BorshSerialize)]
+    pub struct Tile {
+        position: Point,
+        facing: Direction,
+        owner: [u8; 32],
+        height: u128,
+    }
+
+    #[derive(BorshSerialize)]
+    pub struct Player {
+        name: String,
+        tiles: Vec<Tile>,
+        tags: Vec<String>,
+        guild: Option<String>,
+        score: Option<u64>,
+        shape: Shape,
+        marks: HashMap<u32, Vec<u8>>,
+        balance: Balance,
+    }
+
+    #[derive(BorshSerialize)]
+    pub struct Balance {
+        amount: u128,
+    }
+
+    #[derive(BorshSerialize)]
+    pub enum Never {}
+"#;
+
+fn sources() -> Vec<SourceFile> {
+    vec![SourceFile::in_memory("game", SOURCE)]
+}
+
+#[test]
+fn test_fixed_sizes() -> Result<()> {
+    let ir = parse(&sources())?;
+    let (structs, enums) = (ir.struct_map(), ir.enum_map());
+    let analyzer = SizeAnalyzer::new(&structs, &enums);
+
+    assert_eq!(analyzer.size_of("game::Point")?, Size::Fixed(8));
+    assert_eq!(analyzer.size_of("game::Direction")?, Size::Fixed(1));
+    assert_eq!(
+        analyzer.size_of("game::Tile")?,
+        Size::Fixed(8 + 1 + 32 + 16)
+    );
+    assert_eq!(
+        analyzer.type_size(&TypeKind::Array(
+            Box::new(TypeKind::Primitive("u16".to_string())),
+            3
+        ))?,
+        Size::Fixed(6)
+    );
+    assert_eq!(analyzer.size_of("game::Tile")?.to_string(), "57 bytes");
+
+    Ok(())
+}
+
+#[test]
+fn test_variable_sizes() -> Result<()> {
+    let ir = parse(&sources())?;
+    let (structs, enums) = (ir.struct_map(), ir.enum_map());
+    let analyzer = SizeAnalyzer::new(&structs, &enums);
+
+    assert_eq!(
+        analyzer.size_of("game::Shape")?,
+        Size::Variable {
+            min: 9,
+            formula: "1 + match self { Dot => 8, Line => 16 }".to_string()
+        }
+    );
+
+    let player = analyzer.size_of("game::Player")?;
+    assert!(!player.is_fixed());
+    assert_eq!(player.min(), 4 + 4 + 4 + 1 + 1 + 9 + 4 + 16);
+    assert_eq!(
+        player.to_string(),
+        "at least 43 bytes: 35 + len(name) + len(tiles) * 57 + sum(4 + len(tags[i])) \
+         + (guild ? 4 + len(guild) : 0) + (score ? 8 : 0) \
+         + match shape { Dot => 8, Line => 16 } + sum(8 + len(marks.values[i]))"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_sizes() -> Result<()> {
+    let ir = parse(&sources())?;
+    let (structs, enums) = (ir.struct_map(), ir.enum_map());
+    let config = Config {
+        type_overrides: BTreeMap::from([("Balance".to_string(), "b.u128()".to_string())]),
+        ..Config::default()
+    };
+    let analyzer = SizeAnalyzer::new(&structs, &enums).with_config(&config);
+
+    let error = analyzer.size_of("game::Player").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "The size of `Balance` isn't known: it's overridden"
+    );
+    let error = analyzer.size_of("game::Missing").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "The size of `Missing` isn't known: it's not defined"
+    );
+    let error = analyzer.size_of("game::Never").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`Never` has no variants, so it has no values"
+    );
+
+    let sizes = analyzer.sizes();
+    let paths: Vec<_> = sizes.iter().map(|(path, _)| path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "game::Balance",
+            "game::Direction",
+            "game::Never",
+            "game::Player",
+            "game::Point",
+            "game::Shape",
+            "game::Tile"
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_fixed_size_constants() -> Result<()> {
+    let generated = generate(
+        Config {
+            size_constants: true,
+            barrels: true,
+            ..Config::default()
+        },
+        &sources(),
+    )?;
+
+    let game = &generated.files[&PathBuf::from("game.ts")];
+    assert!(game.contains(
+        "export type Point = b.infer<typeof PointSchema>;\n\nexport const PointFixedSize = 8;\n"
+    ));
+    assert!(game.contains("export const DirectionFixedSize = 1;\n"));
+    assert!(game.contains("export const TileFixedSize = 57;\n"));
+    assert!(!game.contains("PlayerFixedSize"));
+    assert!(!game.contains("ShapeFixedSize"));
+    assert!(generated.files[&PathBuf::from("index.ts")].contains("export * from './game';"));
+
+    let generated = generate(Config::default(), &sources())?;
+    assert!(!generated.files[&PathBuf::from("game.ts")].contains("FixedSize"));
+
+    Ok(())
+}